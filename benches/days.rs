@@ -0,0 +1,268 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use advent_of_code_2023::day03::EngineSchematic;
+use advent_of_code_2023::generate;
+use advent_of_code_2023::{day01, day02, day03, day04, day05, day06, day07, day08, day09};
+
+/// A named part of a day, run on the parsed input.
+type Part<'a, T> = (&'a str, &'a dyn Fn(&T));
+
+/// Benchmarks parsing every input, and then every part on the parsed input.
+fn bench_day<T>(
+    c: &mut Criterion,
+    day: &str,
+    inputs: &[(&str, String)],
+    parse: impl Fn(&[u8]) -> T,
+    parts: &[Part<T>],
+) {
+    let mut group = c.benchmark_group(day);
+
+    for (name, input) in inputs {
+        group.bench_with_input(BenchmarkId::new("parse", name), input, |b, input| {
+            b.iter(|| parse(black_box(input.as_bytes())))
+        });
+
+        let parsed = parse(input.as_bytes());
+
+        for (part, run) in parts {
+            group.bench_with_input(BenchmarkId::new(*part, name), &parsed, |b, parsed| {
+                b.iter(|| run(black_box(parsed)))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn day_01(c: &mut Criterion) {
+    let inputs = [
+        ("example", DAY_01_EXAMPLE.to_string()),
+        ("large", generate::day01(100_000, 0x2023_0001)),
+    ];
+
+    bench_day(c, "day-01", &inputs, |input| day01::parse_input(input).unwrap(), &[
+        ("part 2", &|lines: &Vec<_>| { black_box(day01::part2(lines).unwrap()); }),
+    ]);
+}
+
+fn day_02(c: &mut Criterion) {
+    let inputs = [
+        ("example", DAY_02_EXAMPLE.to_string()),
+        ("large", generate::day02(2_000, 0x2023_0002)),
+    ];
+
+    bench_day(c, "day-02", &inputs, |input| day02::parse_input(input).unwrap(), &[
+        ("part 1", &|games: &Vec<_>| { black_box(day02::part1(games).unwrap()); }),
+        ("part 2", &|games: &Vec<_>| { black_box(day02::part2(games).unwrap()); }),
+    ]);
+}
+
+/// The parts of day 03 run on an [`EngineSchematic`], which borrows the parsed rows, so building it
+/// is timed as part of parsing.
+fn day_03(c: &mut Criterion) {
+    let inputs = [
+        ("example", DAY_03_EXAMPLE.to_string()),
+        ("large", generate::day03(1_000, 0x2023_0003)),
+    ];
+    let mut group = c.benchmark_group("day-03");
+
+    for (name, input) in &inputs {
+        group.bench_with_input(BenchmarkId::new("parse", name), input, |b, input| {
+            b.iter(|| {
+                let rows = day03::parse_input(black_box(input.as_bytes())).unwrap();
+                EngineSchematic::new(&rows).unwrap().get_numbers().len()
+            })
+        });
+
+        let rows = day03::parse_input(input.as_bytes()).unwrap();
+        let schematic = EngineSchematic::new(&rows).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("part 1", name), &schematic, |b, schematic| {
+            b.iter(|| day03::part1(black_box(schematic)))
+        });
+        group.bench_with_input(BenchmarkId::new("part 2", name), &schematic, |b, schematic| {
+            b.iter(|| day03::part2(black_box(schematic)))
+        });
+    }
+
+    group.finish();
+}
+
+fn day_04(c: &mut Criterion) {
+    let inputs = [
+        ("example", DAY_04_EXAMPLE.to_string()),
+        ("large", generate::day04(100_000, 0x2023_0004)),
+    ];
+
+    bench_day(c, "day-04", &inputs, |input| day04::parse_input(input).unwrap(), &[
+        ("part 1", &|cards: &Vec<_>| { black_box(day04::part1(cards).unwrap()); }),
+        ("part 2", &|cards: &Vec<_>| { black_box(day04::part2(cards).unwrap()); }),
+    ]);
+}
+
+fn day_05(c: &mut Criterion) {
+    let inputs = [
+        ("example", DAY_05_EXAMPLE.to_string()),
+        ("large", generate::day05(10, 50, 0x2023_0005)),
+    ];
+
+    bench_day(c, "day-05", &inputs, |input| day05::parse_input(input).unwrap(), &[
+        ("part 1", &|almanac| { black_box(day05::part1(almanac).unwrap()); }),
+        ("part 2", &|almanac| { black_box(day05::part2(almanac).unwrap()); }),
+    ]);
+}
+
+fn day_06(c: &mut Criterion) {
+    let inputs = [
+        ("example", DAY_06_EXAMPLE.to_string()),
+        ("large", generate::day06(6, 0x2023_0006)),
+    ];
+
+    bench_day(c, "day-06", &inputs, |input| day06::parse_input(input).unwrap(), &[
+        ("part 1", &|races| { black_box(day06::part1(races).unwrap()); }),
+        ("part 2", &|races| { black_box(day06::part2(races).unwrap()); }),
+    ]);
+}
+
+fn day_07(c: &mut Criterion) {
+    let inputs = [
+        ("example", DAY_07_EXAMPLE.to_string()),
+        ("large", generate::day07(100_000, 0x2023_0007)),
+    ];
+
+    bench_day(c, "day-07", &inputs, |input| day07::parse_input(input).unwrap(), &[
+        ("part 2", &|hands: &Vec<_>| { black_box(day07::part2(hands).unwrap()); }),
+    ]);
+}
+
+fn day_08(c: &mut Criterion) {
+    let inputs = [
+        ("example", DAY_08_EXAMPLE.to_string()),
+        ("large", generate::day08(6, 2_000, 6_000, 0x2023_0008)),
+    ];
+
+    bench_day(c, "day-08", &inputs, |input| day08::parse_input(input).unwrap(), &[
+        ("part 1", &|network| { black_box(day08::part1(network).unwrap()); }),
+        ("part 2", &|network| { black_box(day08::part2(network).unwrap()); }),
+    ]);
+}
+
+fn day_09(c: &mut Criterion) {
+    let inputs = [
+        ("example", DAY_09_EXAMPLE.to_string()),
+        ("large", generate::day09(10_000, 0x2023_0009)),
+    ];
+
+    bench_day(c, "day-09", &inputs, |input| day09::parse_input(input).unwrap(), &[
+        ("part 1", &|sequences: &Vec<_>| { black_box(day09::part1(sequences).unwrap()); }),
+        ("part 2", &|sequences: &Vec<_>| { black_box(day09::part2(sequences).unwrap()); }),
+    ]);
+}
+
+criterion_group!(days, day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09);
+criterion_main!(days);
+
+const DAY_01_EXAMPLE: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+const DAY_02_EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+const DAY_03_EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+const DAY_04_EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+const DAY_05_EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+const DAY_06_EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+const DAY_07_EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+const DAY_08_EXAMPLE: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+const DAY_09_EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use advent_of_code_2023::day04::Card;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(str) = std::str::from_utf8(data) {
+        let _ = Card::parse(str);
+    }
+});
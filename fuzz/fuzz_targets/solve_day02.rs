@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use advent_of_code_2023::day02;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = day02::solve(data);
+});
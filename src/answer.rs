@@ -0,0 +1,110 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The answer to a part of a day. Integers of every width convert into it, non-negative signed
+/// integers are stored as unsigned ones so equal numbers compare equal whatever their type.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Answer {
+    Unsigned(u128),
+    Signed(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(number) => write!(f, "{}", number),
+            Answer::Signed(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($type:ty),*) => {
+        $(impl From<$type> for Answer {
+            fn from(number: $type) -> Self {
+                Answer::Unsigned(number as u128)
+            }
+        })*
+    };
+}
+
+macro_rules! from_signed {
+    ($($type:ty),*) => {
+        $(impl From<$type> for Answer {
+            fn from(number: $type) -> Self {
+                match u128::try_from(number) {
+                    Ok(number) => Answer::Unsigned(number),
+                    Err(_) => Answer::Signed(number as i128),
+                }
+            }
+        })*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// Reads a stored answer: a number when it is one, text otherwise.
+impl FromStr for Answer {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let str = str.trim();
+
+        Ok(match (str.parse::<u128>(), str.parse::<i128>()) {
+            (Ok(number), _) => Answer::Unsigned(number),
+            (_, Ok(number)) => Answer::from(number),
+            _ => Answer::Text(str.to_string()),
+        })
+    }
+}
+
+/// Compares with a stored answer, so `"0042"` and `"42"` both match the number 42. Text only
+/// matches the same text.
+impl PartialEq<str> for Answer {
+    fn eq(&self, stored: &str) -> bool {
+        match self {
+            Answer::Text(text) => text == stored.trim(),
+            _ => stored.parse().is_ok_and(|stored: Answer| *self == stored),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_of_every_width_compare_equal() {
+        assert_eq!(Answer::from(42u16), Answer::from(42u128));
+        assert_eq!(Answer::from(42i64), Answer::from(42u32));
+        assert_eq!(Answer::from(-2i64), Answer::Signed(-2));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn compares_with_stored_answers() {
+        assert!(Answer::from(35u64) == *"35");
+        assert!(Answer::from(35u64) == *"0035");
+        assert!(Answer::from(-2i64) == *"-2");
+        assert!(Answer::from(35u64) != *"36");
+
+        let text = Answer::from("35");
+        assert!(text == *"35");
+        assert!(text != *"0035");
+    }
+}
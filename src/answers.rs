@@ -0,0 +1,194 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{value, DocumentMut, Item, Table};
+
+use crate::answer::Answer;
+use crate::input::{take_flag, Source};
+use crate::output::PartResult;
+use crate::solution::Solution;
+
+/// `answers.toml` next to `Cargo.toml`.
+pub fn answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+    /// Unknown, and added to the registry with `--record`.
+    Recorded,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong { expected } => write!(f, "WRONG, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+            Verdict::Recorded => write!(f, "recorded"),
+        }
+    }
+}
+
+/// Known answers, stored as `[day-05.example1]` tables with a `part1` and `part2` string each.
+#[derive(Debug)]
+pub struct Registry {
+    path: PathBuf,
+    document: DocumentMut,
+}
+
+impl Registry {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let document = match fs::read_to_string(path) {
+            Ok(contents) => contents.parse()
+                .map_err(|err| format!("invalid {}: {}", path.display(), err))?,
+            Err(_) => DocumentMut::new(),
+        };
+
+        Ok(Self { path: path.to_path_buf(), document })
+    }
+
+    fn day_key(day: u8) -> String {
+        format!("day-{:02}", day)
+    }
+
+    fn part_key(part: u8) -> String {
+        format!("part{}", part)
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: u8) -> Option<&str> {
+        self.document.get(&Self::day_key(day))?
+            .get(input)?
+            .get(Self::part_key(part))?
+            .as_str()
+    }
+
+    pub fn check(&self, day: u8, input: &str, part: u8, answer: &Answer) -> Verdict {
+        match self.get(day, input, part) {
+            Some(expected) if answer == expected => Verdict::Correct,
+            Some(expected) => Verdict::Wrong { expected: expected.to_string() },
+            None => Verdict::Unknown,
+        }
+    }
+
+    pub fn insert(&mut self, day: u8, input: &str, part: u8, answer: &Answer) {
+        let day_table = self.document
+            .entry(&Self::day_key(day))
+            .or_insert_with(|| {
+                let mut table = Table::new();
+                table.set_implicit(true);
+                Item::Table(table)
+            });
+
+        let input_table = day_table.as_table_mut()
+            .expect("day entries in the registry are tables")
+            .entry(input)
+            .or_insert_with(|| Item::Table(Table::new()));
+
+        input_table[Self::part_key(part)] = value(answer.to_string());
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        fs::write(&self.path, self.document.to_string())
+            .map_err(|err| format!("could not write {}: {}", self.path.display(), err))
+    }
+}
+
+/// Prints a day's answers, marking each one as correct, wrong or unknown according to the
+/// registry. With `--record`, unknown answers are added to the registry when the report is
+/// finished.
+#[derive(Debug)]
+pub struct Report {
+    day: u8,
+    /// Name of the input, `None` when it isn't from the store.
+    input: Option<String>,
+    source: String,
+    registry: Registry,
+    record: bool,
+    recorded: usize,
+    /// Parts whose answers belong to a variation of the puzzle, which the registry doesn't know.
+    unchecked: Vec<u8>,
+}
+
+impl Report {
+    /// Creates a report for the input, taking `--record` out of `args`.
+    pub fn new(day: u8, source: &Source, args: &mut Vec<String>) -> Self {
+        let record = take_flag(args, "--record");
+
+        // Only the named inputs in the store can be looked up again later
+        let input = match source {
+            Source::Real | Source::Example(_) => Some(source.name()),
+            Source::Stdin | Source::Path(_) => None,
+        };
+
+        if record && input.is_none() {
+            eprintln!("warning: answers for {} can't be recorded, use the inputs/ store", source.name());
+        }
+
+        let registry = Registry::load(&answers_path()).unwrap_or_else(|err| {
+            eprintln!("warning: {}", err);
+            Registry { path: answers_path(), document: DocumentMut::new() }
+        });
+
+        Self { day, input, source: source.name(), registry, record, recorded: 0, unchecked: vec![] }
+    }
+
+    /// Leaves the answers to `part` unknown and unrecorded, for answers to a variation of the
+    /// puzzle, such as another scoring rule.
+    pub fn leave_unchecked(&mut self, part: u8) {
+        self.unchecked.push(part);
+    }
+
+    /// Checks the answer to a part against the registry, recording it when it's unknown and
+    /// `--record` was given.
+    pub fn check(&mut self, part: u8, answer: &Answer) -> Verdict {
+        let Some(input) = self.input.as_ref().filter(|_| !self.unchecked.contains(&part)) else {
+            return Verdict::Unknown;
+        };
+
+        match self.registry.check(self.day, input, part, answer) {
+            Verdict::Unknown if self.record => {
+                self.registry.insert(self.day, input, part, answer);
+                self.recorded += 1;
+                Verdict::Recorded
+            }
+            verdict => verdict,
+        }
+    }
+
+    /// Prints `<label> = <answer>` followed by its verdict.
+    pub fn answer(&mut self, part: u8, label: &str, answer: impl Into<Answer>) {
+        let answer = answer.into();
+        let verdict = self.check(part, &answer);
+
+        println!("{} = {} ({})", label, answer, verdict);
+    }
+
+    /// Checks the answer to every part of the solution.
+    pub fn results(&mut self, solution: &Solution) -> Vec<PartResult> {
+        solution.parts.iter()
+            .map(|part| PartResult {
+                day: solution.day,
+                part: part.part,
+                input: self.input.clone().unwrap_or_else(|| self.source.clone()),
+                answer: part.answer.clone(),
+                verdict: self.check(part.part, &part.answer),
+                parse: solution.parse,
+                measurement: part.measurement,
+            })
+            .collect()
+    }
+
+    /// Saves newly recorded answers.
+    pub fn finish(self) {
+        if self.recorded > 0 {
+            if let Err(err) = self.registry.save() {
+                eprintln!("error: {}", err);
+            }
+        }
+    }
+}
@@ -1,69 +1,27 @@
-use std::io;
+use advent_of_code_2023::answers::Report;
+use advent_of_code_2023::day01::{parse_input, part2, solve};
+use advent_of_code_2023::input;
+use advent_of_code_2023::output::Output;
+use advent_of_code_2023::timings::CountingAllocator;
 
-static DIGITS: [(&str, u32); 10] = [
-    ("zero", 0),
-    ("one", 1),
-    ("two", 2),
-    ("three", 3),
-    ("four", 4),
-    ("five", 5),
-    ("six", 6),
-    ("seven", 7),
-    ("eight", 8),
-    ("nine", 9)
-];
-
-fn find_digit(chars: Vec<char>, from_right: bool) -> u32 {
-    let max = chars.len();
-
-    let mut offset = 0;
-    let mut result: Option<u32> = None;
-
-    while offset < max && result.is_none() {
-        if chars[offset].is_ascii_digit() {
-            result = Some(chars[offset].to_digit(10).unwrap());
-            break
-        }
-
-        for (name, digit) in DIGITS {
-            if (offset + 1) < name.len() {
-                continue
-            }
-
-            let subset = &chars[((offset + 1) - name.len())..=offset];
-
-            let string = if from_right {
-                String::from_iter(subset.iter().rev())
-            } else {
-                String::from_iter(subset)
-            };
-
-            if string == name {
-                result = Some(digit);
-                break
-            }
-        }
-
-        offset += 1;
-    }
-
-    result.expect("Couldn't find a digit")
-}
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 fn main() {
-    let mut buffer = String::new();
-    let mut total: u128 = 0;
+    let (mut input, mut args) = input::open(1);
+    let mut report = Report::new(1, &input.source, &mut args);
 
-    while io::stdin().read_line(&mut buffer).unwrap() != 0 {
-        let first_digit = find_digit(buffer.trim().chars().collect(), false);
-        let last_digit = find_digit(buffer.trim().chars().rev().collect(), true);
+    let output = input::exit_on_error(Output::from_args(&mut args));
 
-        let number = first_digit * 10 + last_digit;
-
-        total += number as u128;
-
-        buffer.clear();
+    if output.results_only() {
+        let solution = input::exit_on_error(solve(&mut input));
+        output.print(&report.results(&solution), &[solution]);
+        report.finish();
+        return;
     }
 
-    println!("total = {}", total);
+    let lines = input::exit_on_error(parse_input(&mut input));
+
+    report.answer(2, "total", input::exit_on_error(part2(&lines)));
+    report.finish();
 }
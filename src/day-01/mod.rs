@@ -0,0 +1,86 @@
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::input::read_error;
+use crate::solution::Solution;
+
+static DIGITS: [(&str, u32); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9)
+];
+
+fn find_digit(chars: Vec<char>, from_right: bool) -> Option<u32> {
+    let max = chars.len();
+
+    let mut offset = 0;
+    let mut result: Option<u32> = None;
+
+    while offset < max && result.is_none() {
+        if chars[offset].is_ascii_digit() {
+            result = Some(chars[offset].to_digit(10).unwrap());
+            break
+        }
+
+        for (name, digit) in DIGITS {
+            if (offset + 1) < name.len() {
+                continue
+            }
+
+            let subset = &chars[((offset + 1) - name.len())..=offset];
+
+            let string = if from_right {
+                String::from_iter(subset.iter().rev())
+            } else {
+                String::from_iter(subset)
+            };
+
+            if string == name {
+                result = Some(digit);
+                break
+            }
+        }
+
+        offset += 1;
+    }
+
+    result
+}
+
+pub fn parse_input(input: impl BufRead) -> Result<Vec<String>, String> {
+    input.lines()
+        .map(|line| line.map(|line| line.trim().to_string()).map_err(read_error))
+        .collect()
+}
+
+/// Sum of the calibration values, with spelled out digits.
+pub fn part2(lines: &[String]) -> Result<Answer, String> {
+    lines.iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let first_digit = find_digit(line.chars().collect(), false);
+            let last_digit = find_digit(line.chars().rev().collect(), true);
+
+            match (first_digit, last_digit) {
+                (Some(first_digit), Some(last_digit)) => Ok((first_digit * 10 + last_digit) as u128),
+                _ => Err(format!("no digit on line {}", index + 1)),
+            }
+        })
+        .sum::<Result<u128, String>>()
+        .map(Answer::from)
+}
+
+/// Solves part 2; the first example has no spelled out digits to solve it for.
+pub fn solve(input: impl BufRead) -> Result<Solution, String> {
+    let mut solution = Solution::new(1);
+    let lines = solution.parse(|| parse_input(input))?;
+    solution.solve(2, || part2(&lines))?;
+    Ok(solution)
+}
@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+
+use super::{BagConfiguration, Game};
+
+/// Bag configurations admitting exactly a chosen subset of games. Any bag between `minimal` and
+/// one of the `maximal` bags (component-wise) admits that subset and nothing else. Components of
+/// `u16::MAX` in a maximal bag are unbounded.
+#[derive(Debug)]
+pub struct Feasibility {
+    pub minimal: BagConfiguration,
+    pub maximal: Vec<BagConfiguration>,
+}
+
+/// The smallest bag for which every game is possible.
+pub fn minimal_bag<'a>(games: impl IntoIterator<Item = &'a Game>) -> BagConfiguration {
+    games.into_iter()
+        .fold(BagConfiguration::new(0, 0, 0), |bag, game| BagConfiguration {
+            red: bag.red.max(game.max_red),
+            green: bag.green.max(game.max_green),
+            blue: bag.blue.max(game.max_blue),
+        })
+}
+
+/// The Pareto frontier of bags admitting exactly the games with the given ids, or `None` when
+/// every bag admitting those games also admits another one.
+pub fn exact_subset(games: &[Game], ids: &HashSet<u16>) -> Option<Feasibility> {
+    let (included, excluded): (Vec<&Game>, Vec<&Game>) = games.iter()
+        .partition(|game| ids.contains(&game.id));
+
+    let minimal = minimal_bag(included);
+
+    if excluded.iter().any(|game| game.is_possible(&minimal)) {
+        return None;
+    }
+
+    // An excluded game is ruled out by keeping at least one colour below its maximum, so the
+    // largest bags only ever sit just below an excluded game's maximum or are unbounded.
+    let thresholds = |amount: fn(&Game) -> u16, minimum: u16| -> Vec<u16> {
+        let mut values: Vec<u16> = excluded.iter()
+            .map(|game| amount(game))
+            .filter(|amount| *amount > minimum)
+            .map(|amount| amount - 1)
+            .chain([u16::MAX])
+            .collect();
+        values.sort_unstable();
+        values.dedup();
+        values
+    };
+
+    let mut candidates = vec![];
+
+    for red in thresholds(|game| game.max_red, minimal.red) {
+        for green in thresholds(|game| game.max_green, minimal.green) {
+            // Games not ruled out by red or green have to be ruled out by blue
+            let blue_limits: Option<Vec<u16>> = excluded.iter()
+                .filter(|game| game.max_red <= red && game.max_green <= green)
+                .map(|game| game.max_blue.checked_sub(1))
+                .collect();
+
+            let Some(blue) = blue_limits.map(|limits| limits.into_iter().min().unwrap_or(u16::MAX)) else {
+                continue;
+            };
+
+            if blue >= minimal.blue {
+                candidates.push(BagConfiguration::new(red, green, blue));
+            }
+        }
+    }
+
+    let maximal = candidates.iter()
+        .filter(|bag| !candidates.iter().any(|other| other.dominates(bag)))
+        .copied()
+        .collect();
+
+    Some(Feasibility { minimal, maximal })
+}
+
+/// The bag holding at most `budget` cubes that maximizes the sum of the ids of possible games,
+/// together with that sum.
+pub fn best_bag_for_budget(games: &[Game], budget: u32) -> (BagConfiguration, u32) {
+    let amounts = |amount: fn(&Game) -> u16| -> Vec<u16> {
+        let mut values: Vec<u16> = games.iter()
+            .map(amount)
+            .filter(|amount| (*amount as u32) <= budget)
+            .chain([0])
+            .collect();
+        values.sort_unstable();
+        values.dedup();
+        values
+    };
+
+    let mut best = (BagConfiguration::new(0, 0, 0), 0);
+
+    for red in amounts(|game| game.max_red) {
+        for green in amounts(|game| game.max_green) {
+            let used = red as u32 + green as u32;
+            if used > budget {
+                break;
+            }
+
+            let blue = (budget - used).min(u16::MAX as u32) as u16;
+            let bag = BagConfiguration::new(red, green, blue);
+
+            let sum: u32 = games.iter()
+                .filter(|game| game.is_possible(&bag))
+                .map(|game| game.id as u32)
+                .sum();
+
+            if sum > best.1 {
+                best = (bag, sum);
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day02::parse_input;
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn possible_ids(games: &[Game], bag: &BagConfiguration) -> HashSet<u16> {
+        games.iter().filter(|game| game.is_possible(bag)).map(|game| game.id).collect()
+    }
+
+    #[test]
+    fn exact_subset_frontier() {
+        let games = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let ids = HashSet::from([1, 2, 5]);
+        let feasibility = exact_subset(&games, &ids).unwrap();
+
+        assert_eq!(feasibility.minimal, BagConfiguration::new(6, 3, 6));
+        assert_eq!(feasibility.maximal, vec![
+            BagConfiguration::new(13, u16::MAX, u16::MAX),
+            BagConfiguration::new(19, u16::MAX, 14),
+            BagConfiguration::new(u16::MAX, 12, 14),
+        ]);
+
+        for bag in feasibility.maximal.iter().chain([&feasibility.minimal]) {
+            assert_eq!(possible_ids(&games, bag), ids, "bag {}", bag);
+        }
+
+        // Every bag admitting game 3 admits games 1, 2 and 5 as well
+        assert!(exact_subset(&games, &HashSet::from([3])).is_none());
+    }
+
+    #[test]
+    fn best_bag_matches_brute_force() {
+        let games = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let sum = |bag: &BagConfiguration| possible_ids(&games, bag).iter().map(|id| *id as u32).sum::<u32>();
+
+        for budget in [0, 10, 25, 39, 45] {
+            let (bag, best) = best_bag_for_budget(&games, budget);
+            let brute_force = (0..=budget as u16)
+                .flat_map(|red| (0..=budget as u16 - red).map(move |green| (red, green)))
+                .map(|(red, green)| sum(&BagConfiguration::new(red, green, budget as u16 - red - green)))
+                .max()
+                .unwrap();
+
+            assert!(bag.total() <= budget);
+            assert_eq!(sum(&bag), best);
+            assert_eq!(best, brute_force, "budget {}", budget);
+        }
+    }
+
+    #[test]
+    fn large_budgets_leave_blue_unbounded() {
+        let games = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let (bag, best) = best_bag_for_budget(&games, 200_000);
+
+        assert_eq!(best, 15);
+        assert_eq!(bag.blue, u16::MAX);
+        assert_eq!(bag.to_string(), "20 red, 13 green, any blue");
+    }
+}
@@ -1,100 +1,48 @@
-use std::io;
-use std::num::ParseIntError;
+use std::collections::HashSet;
 
-use regex::Regex;
+use advent_of_code_2023::answers::Report;
+use advent_of_code_2023::day02::{feasibility, parse_input, part1, part2, solve, BAG};
+use advent_of_code_2023::input;
+use advent_of_code_2023::output::Output;
+use advent_of_code_2023::timings::CountingAllocator;
 
-use crate::GameError::{InvalidGameId, NoGameId};
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
-#[derive(Debug)]
-struct BagConfiguration {
-    pub red: u16,
-    pub green: u16,
-    pub blue: u16,
-}
-
-#[derive(Debug)]
-struct Game {
-    id: u16,
-    max_red: u16,
-    max_green: u16,
-    max_blue: u16,
-}
-
-#[derive(Debug)]
-enum GameError {
-    NoGameId,
-    InvalidGameId(ParseIntError),
-}
-
-impl Game {
-    pub fn parse(str: &str) -> Result<Game, GameError> {
-        let game_split: Vec<&str> = str.split(": ").collect();
-
-        let game_id = game_split[0].strip_prefix("Game ")
-            .ok_or_else(|| NoGameId)?
-            .parse::<u16>()
-            .map_err(|err| InvalidGameId(err))?;
-
-        let mut game = Game {
-            id: game_id,
-            max_red: 0,
-            max_green: 0,
-            max_blue: 0,
-        };
-
-        Regex::new(r"([,;]) ").unwrap()
-            .split(game_split[1].trim())
-            .map(|item| item.split(" ").collect::<Vec<&str>>())
-            .map(|vec| (vec[0].parse::<u16>().unwrap(), vec[1]))
-            .for_each(|(amount, cube_type)| game.add_hand(amount, cube_type));
-
-        Ok(game)
-    }
+fn main() {
+    let (mut input, mut args) = input::open(2);
+    let mut report = Report::new(2, &input.source, &mut args);
 
-    fn add_hand(&mut self, amount: u16, cube_type: &str) {
-        match cube_type {
-            "red" => self.max_red = self.max_red.max(amount),
-            "green" => self.max_green = self.max_green.max(amount),
-            "blue" => self.max_blue = self.max_blue.max(amount),
-            _ => ()
-        };
-    }
+    let output = input::exit_on_error(Output::from_args(&mut args));
 
-    pub fn is_possible(&self, configuration: &BagConfiguration) -> bool {
-        self.max_red <= configuration.red
-            && self.max_blue <= configuration.blue
-            && self.max_green <= configuration.green
+    if output.results_only() {
+        let solution = input::exit_on_error(solve(&mut input));
+        output.print(&report.results(&solution), &[solution]);
+        report.finish();
+        return;
     }
 
-    pub fn power(&self) -> u128 {
-        (self.max_blue as u128) * (self.max_green as u128) * (self.max_red as u128)
-    }
-}
+    let games = input::exit_on_error(parse_input(&mut input));
 
-fn main() {
-    let configuration = BagConfiguration {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
-
-    let mut total_possible: u16 = 0;
-    let mut total_power: u128 = 0;
+    report.answer(1, "total possible", input::exit_on_error(part1(&games)));
+    report.answer(2, "total power   ", input::exit_on_error(part2(&games)));
+    report.finish();
 
-    let mut buffer = String::new();
+    println!("minimal bag    = {}", feasibility::minimal_bag(&games));
 
-    while io::stdin().read_line(&mut buffer).unwrap() != 0 {
-        let game = Game::parse(buffer.as_ref()).unwrap();
+    let possible_ids: HashSet<u16> = games.iter()
+        .filter(|game| game.is_possible(&BAG))
+        .map(|game| game.id)
+        .collect();
 
-        if game.is_possible(&configuration) {
-            total_possible += game.id;
+    if let Some(feasibility) = feasibility::exact_subset(&games, &possible_ids) {
+        println!("bags admitting the same games:");
+        println!("  smallest     = {}", feasibility.minimal);
+        for bag in feasibility.maximal {
+            println!("  largest      = {}", bag);
         }
-
-        total_power += game.power();
-
-        buffer.clear();
     }
 
-    println!("total possible = {}", total_possible);
-    println!("total power    = {}", total_power);
+    let (best_bag, best_sum) = feasibility::best_bag_for_budget(&games, BAG.total());
+    println!("best bag of {} = {} (total possible = {})", BAG.total(), best_bag, best_sum);
 }
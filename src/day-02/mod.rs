@@ -0,0 +1,159 @@
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::num::ParseIntError;
+
+use regex::Regex;
+
+use crate::answer::Answer;
+use crate::input::read_error;
+use crate::solution::Solution;
+
+use GameError::{InvalidAmount, InvalidCubes, InvalidGameId, NoCubes, NoGameId, UnknownColour};
+
+pub mod feasibility;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BagConfiguration {
+    pub red: u16,
+    pub green: u16,
+    pub blue: u16,
+}
+
+impl BagConfiguration {
+    pub fn new(red: u16, green: u16, blue: u16) -> Self {
+        Self { red, green, blue }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.red as u32 + self.green as u32 + self.blue as u32
+    }
+
+    pub fn dominates(&self, other: &Self) -> bool {
+        self != other
+            && self.red >= other.red
+            && self.green >= other.green
+            && self.blue >= other.blue
+    }
+}
+
+impl Display for BagConfiguration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let amount = |amount: u16| if amount == u16::MAX { "any".to_string() } else { amount.to_string() };
+
+        write!(f, "{} red, {} green, {} blue", amount(self.red), amount(self.green), amount(self.blue))
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    pub id: u16,
+    max_red: u16,
+    max_green: u16,
+    max_blue: u16,
+}
+
+#[derive(Debug)]
+pub enum GameError {
+    NoGameId,
+    InvalidGameId(ParseIntError),
+    NoCubes,
+    InvalidCubes(String),
+    InvalidAmount(ParseIntError),
+    UnknownColour(String),
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NoGameId => write!(f, "no game id"),
+            InvalidGameId(err) => write!(f, "invalid game id: {}", err),
+            NoCubes => write!(f, "no ': ' before the cubes"),
+            InvalidCubes(cubes) => write!(f, "invalid cubes '{}', expected an amount and a colour", cubes),
+            InvalidAmount(err) => write!(f, "invalid amount: {}", err),
+            UnknownColour(colour) => write!(f, "unknown colour {}", colour),
+        }
+    }
+}
+
+impl Game {
+    pub fn parse(str: &str) -> Result<Game, GameError> {
+        let (game, draws) = str.split_once(": ").ok_or(NoCubes)?;
+
+        let game_id = game.strip_prefix("Game ")
+            .ok_or(NoGameId)?
+            .parse::<u16>()
+            .map_err(InvalidGameId)?;
+
+        let mut game = Game {
+            id: game_id,
+            max_red: 0,
+            max_green: 0,
+            max_blue: 0,
+        };
+
+        for cubes in Regex::new(r"([,;]) ").unwrap().split(draws.trim()) {
+            let (amount, cube_type) = cubes.split_once(' ')
+                .ok_or_else(|| InvalidCubes(cubes.to_string()))?;
+
+            game.add_hand(amount.parse::<u16>().map_err(InvalidAmount)?, cube_type)?;
+        }
+
+        Ok(game)
+    }
+
+    fn add_hand(&mut self, amount: u16, cube_type: &str) -> Result<(), GameError> {
+        match cube_type {
+            "red" => self.max_red = self.max_red.max(amount),
+            "green" => self.max_green = self.max_green.max(amount),
+            "blue" => self.max_blue = self.max_blue.max(amount),
+            _ => return Err(UnknownColour(cube_type.to_string())),
+        };
+
+        Ok(())
+    }
+
+    pub fn is_possible(&self, configuration: &BagConfiguration) -> bool {
+        self.max_red <= configuration.red
+            && self.max_blue <= configuration.blue
+            && self.max_green <= configuration.green
+    }
+
+    pub fn power(&self) -> u128 {
+        (self.max_blue as u128) * (self.max_green as u128) * (self.max_red as u128)
+    }
+}
+
+/// The bag from the puzzle: 12 red, 13 green and 14 blue cubes.
+pub const BAG: BagConfiguration = BagConfiguration { red: 12, green: 13, blue: 14 };
+
+pub fn parse_input(input: impl BufRead) -> Result<Vec<Game>, String> {
+    input.lines()
+        .enumerate()
+        .map(|(index, line)| Game::parse(&line.map_err(read_error)?)
+            .map_err(|err| format!("invalid game on line {}: {}", index + 1, err)))
+        .collect()
+}
+
+/// Sum of the ids of the games possible with [`BAG`].
+pub fn part1(games: &[Game]) -> Result<Answer, String> {
+    Ok(games.iter()
+        .filter(|game| game.is_possible(&BAG))
+        .map(|game| game.id as u64)
+        .sum::<u64>()
+        .into())
+}
+
+/// Sum of the power of the minimal bag for every game.
+pub fn part2(games: &[Game]) -> Result<Answer, String> {
+    Ok(games.iter()
+        .map(Game::power)
+        .sum::<u128>()
+        .into())
+}
+
+pub fn solve(input: impl BufRead) -> Result<Solution, String> {
+    let mut solution = Solution::new(2);
+    let games = solution.parse(|| parse_input(input))?;
+    solution.solve_both(|| part1(&games), || part2(&games))?;
+    Ok(solution)
+}
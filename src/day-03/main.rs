@@ -1,242 +1,195 @@
-use std::collections::HashSet;
 use std::io;
+use std::io::{BufRead, BufWriter, Write};
+
+use advent_of_code_2023::answers::Report;
+use advent_of_code_2023::day03::query::Aggregate;
+use advent_of_code_2023::day03::render::{self, RenderFormat};
+use advent_of_code_2023::day03::stream::{SchematicWindow, StreamEvent};
+use advent_of_code_2023::day03::{parse_input, part1, part2, solve, EngineSchematic};
+use advent_of_code_2023::input;
+use advent_of_code_2023::input::{Input, InputError};
+use advent_of_code_2023::output::Output;
+use advent_of_code_2023::timings::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Arguments for querying symbols: `--symbols <chars>`, `--adjacent <count>` and
+/// `--aggregate sum|product|min|max`.
+#[derive(Debug)]
+struct QueryArgs {
+    symbols: String,
+    adjacent: Option<usize>,
+    aggregate: Aggregate,
+}
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Direction { Left, Right }
+#[derive(Debug, Default)]
+struct Args {
+    query: Option<QueryArgs>,
+    render: Option<RenderFormat>,
+    stream: bool,
+}
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-struct Position(i32, i32);
+fn parse_args(args: Vec<String>) -> Result<Args, InputError> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
 
-impl Position {
-    pub fn go(&self, direction: Direction) -> Position {
-        match direction {
-            Direction::Left => Position(self.0 - 1, self.1),
-            Direction::Right => Position(self.0 + 1, self.1)
+    while let Some(arg) = args.next() {
+        if arg == "--stream" {
+            parsed.stream = true;
+            continue;
         }
-    }
 
-    pub fn surrounding(&self, width: usize, height: usize) -> Vec<Self> {
-        let mut positions = vec![];
+        let value = args.next()
+            .ok_or_else(|| InputError::InvalidArgument(format!("missing value for {}", arg)))?;
 
-        let start_x = self.0 - 1;
-        let stop_x = self.0 + width as i32;
-        let start_y = self.1 - 1;
-        let stop_y = self.1 + height as i32;
-
-        for x in start_x..=stop_x {
-            positions.push(Position(x, start_y));
-            positions.push(Position(x, stop_y));
+        if arg == "--render" {
+            parsed.render = Some(input::parse_value(&arg, &value)?);
+            continue;
         }
 
-        for y in (start_y + 1)..stop_y {
-            positions.push(Position(start_x, y));
-            positions.push(Position(stop_x, y));
+        let query = parsed.query.get_or_insert_with(|| QueryArgs {
+            symbols: String::new(),
+            adjacent: None,
+            aggregate: Aggregate::Sum,
+        });
+
+        match arg.as_str() {
+            "--symbols" => query.symbols = value,
+            "--adjacent" => query.adjacent = Some(input::parse_value(&arg, &value)?),
+            "--aggregate" => query.aggregate = input::parse_value(&arg, &value)?,
+            _ => return Err(InputError::InvalidArgument(format!("unknown argument {}", arg))),
         }
-
-        positions
     }
-}
 
-#[derive(Debug, Eq, PartialEq, Hash)]
-struct EngineSchematicNumber {
-    number: u16,
-    position: Position,
-    width: usize,
-    height: usize,
+    Ok(parsed)
 }
 
-impl EngineSchematicNumber {
-    pub fn new(number_data: &str, last_x: i32, last_y: i32) -> Self {
-        EngineSchematicNumber {
-            number: number_data.parse().unwrap(),
-            position: Position(
-                last_x - number_data.len() as i32,
-                last_y,
-            ),
-            width: number_data.len(),
-            height: 1,
-        }
-    }
-}
+fn print_query(schematic: &EngineSchematic, query: &QueryArgs) {
+    let matches = schematic.query(
+        |symbol| query.symbols.is_empty() || query.symbols.contains(symbol),
+        |count| query.adjacent.map_or(count > 0, |adjacent| count == adjacent),
+    );
 
-#[derive(Debug)]
-struct EngineSchematic<'a> {
-    width: usize,
-    height: usize,
-    schematic: &'a Vec<Vec<char>>,
-}
-
-impl<'a> EngineSchematic<'a> {
-    pub fn new(schematic: &'a Vec<Vec<char>>) -> Self {
-        if schematic.len() == 0 {
-            panic!("Invalid schematic length");
-        }
+    for symbol_match in &matches {
+        let numbers: Vec<String> = symbol_match.numbers.iter()
+            .map(|number| number.number.to_string())
+            .collect();
 
-        EngineSchematic {
-            schematic,
-            width: schematic[0].len(),
-            height: schematic.len(),
-        }
+        println!(
+            "{} at ({}, {})\t[{}]\t{:?} = {}",
+            symbol_match.symbol,
+            symbol_match.position.0,
+            symbol_match.position.1,
+            numbers.join(", "),
+            query.aggregate,
+            symbol_match.aggregate(query.aggregate).unwrap_or(0),
+        );
     }
 
-    fn get_char(&self, pos: &Position) -> char {
-        self.schematic[pos.1 as usize][pos.0 as usize]
-    }
+    let total: u128 = matches.iter()
+        .filter_map(|symbol_match| symbol_match.aggregate(query.aggregate))
+        .sum();
 
-    fn is_in_bounds(&self, pos: &Position) -> bool {
-        pos.0 >= 0
-            && pos.0 < self.width as i32
-            && pos.1 >= 0
-            && pos.1 < self.height as i32
-    }
+    println!("sum of {:?} over {} symbols = {}", query.aggregate, matches.len(), total);
+}
 
-    pub fn get_gears(&self) -> Vec<Position> {
-        let mut parts = Vec::new();
+/// Streams the schematic from the input with a three row window, printing every part number and gear
+/// ratio as soon as its row is complete. Returns the sum of part numbers and of gear ratios.
+fn stream_schematic(input: &mut Input) -> Result<(u128, u128), String> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut window = SchematicWindow::new();
+    let mut buffer = String::new();
 
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let position = Position(x as i32, y as i32);
-                let char = self.get_char(&position);
+    let mut sum_part_numbers: u128 = 0;
+    let mut sum_gear_ratios: u128 = 0;
 
-                if char == '*' {
-                    parts.push(position);
+    let mut emit = |events: Vec<StreamEvent>| -> Result<(), String> {
+        for event in events {
+            match event {
+                StreamEvent::PartNumber { row, column, number } => {
+                    sum_part_numbers = sum_part_numbers.checked_add(number)
+                        .ok_or("sum of part numbers doesn't fit in 128 bits")?;
+                    writeln!(out, "part {} at ({}, {})", number, column, row).unwrap();
                 }
-            }
-        }
-
-        parts
-    }
-
-    pub fn get_numbers(&self) -> Vec<EngineSchematicNumber> {
-        let mut numbers = Vec::new();
-        let mut number = String::new();
-
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if self.schematic[y][x].is_ascii_digit() {
-                    number.push(self.schematic[y][x]);
-                } else if !number.is_empty() {
-                    numbers.push(EngineSchematicNumber::new(number.as_ref(), x as i32, y as i32));
-                    number.clear();
+                StreamEvent::GearRatio { row, column, ratio } => {
+                    sum_gear_ratios = sum_gear_ratios.checked_add(ratio)
+                        .ok_or("sum of gear ratios doesn't fit in 128 bits")?;
+                    writeln!(out, "gear {} at ({}, {})", ratio, column, row).unwrap();
                 }
             }
-
-            if !number.is_empty() {
-                numbers.push(EngineSchematicNumber::new(number.as_ref(), self.width as i32, y as i32));
-                number.clear();
-            }
         }
 
-        numbers
-    }
-
-    pub fn get_surrounding_parts(&self, number: &EngineSchematicNumber) -> Vec<(Position, char)> {
-        let positions = number.position.surrounding(number.width, number.height);
-
-        positions
-            .iter()
-            .filter(|position| self.is_in_bounds(position))
-            .map(|position| (position, self.get_char(position)))
-            .filter(|(_, char)| !char.is_ascii_digit() && *char != '.')
-            .map(|(position, char)| (position.clone(), char))
-            .collect()
-    }
-
-    pub fn get_gear_ratio(&self, position: &Position) -> Option<u128> {
-        let positions = position.surrounding(1, 1);
-
-        let surrounding: HashSet<EngineSchematicNumber> = positions
-            .iter()
-            .filter(|position| self.is_in_bounds(position))
-            .map(|position| self.find_number(position))
-            .filter(|number| number.is_some())
-            .map(|number| number.unwrap())
-            .collect();
+        Ok(())
+    };
 
-        if surrounding.len() == 2 {
-            Some(surrounding.iter()
-                .map(|number| number.number as u128)
-                .product()
-            )
-        } else {
-            None
-        }
+    while input.read_line(&mut buffer).map_err(input::read_error)? != 0 {
+        emit(window.push(buffer.trim().chars().collect())?)?;
+        buffer.clear();
     }
 
-    fn find_number(&self, position: &Position) -> Option<EngineSchematicNumber> {
-        let starting_char = self.get_char(position);
-
-        if !starting_char.is_ascii_digit() {
-            return None;
-        }
+    emit(window.finish())?;
 
-        let mut characters = vec![starting_char];
-        let mut left_pos = *position;
-        loop {
-            left_pos = left_pos.go(Direction::Left);
-            if !self.is_in_bounds(&left_pos) {
-                break;
-            }
-
-            let char = self.get_char(&left_pos);
-            if char.is_ascii_digit() {
-                characters.insert(0, char);
-            } else {
-                break;
-            }
-        }
+    out.flush().unwrap();
 
-        let mut right_pos = *position;
-        loop {
-            right_pos = right_pos.go(Direction::Right);
-            if !self.is_in_bounds(&right_pos) {
-                break;
-            }
+    Ok((sum_part_numbers, sum_gear_ratios))
+}
 
-            let char = self.get_char(&right_pos);
-            if char.is_ascii_digit() {
-                characters.push(char);
-            } else {
-                break;
+fn main() {
+    let (mut input, mut args) = input::open(3);
+    let mut report = Report::new(3, &input.source, &mut args);
+
+    let output = input::exit_on_error(Output::from_args(&mut args));
+    let args = input::exit_on_error(parse_args(args));
+
+    if output.results_only() {
+        // Streaming, rendering and queries print their own output
+        let flags = [
+            ("--stream", args.stream),
+            ("--render", args.render.is_some()),
+            ("--symbols/--adjacent/--aggregate", args.query.is_some()),
+        ];
+
+        for (flag, given) in flags {
+            if given {
+                input::exit_on_error(output.check_flag(flag));
             }
         }
 
-        Some(EngineSchematicNumber::new(
-            &String::from_iter(characters),
-            left_pos.0,
-            left_pos.1,
-        ))
+        let solution = input::exit_on_error(solve(&mut input));
+        output.print(&report.results(&solution), &[solution]);
+        report.finish();
+        return;
     }
-}
 
+    if args.stream {
+        let (sum_part_numbers, sum_gear_ratios) = input::exit_on_error(stream_schematic(&mut input));
+        report.answer(1, "sum part numbers", sum_part_numbers);
+        report.answer(2, "sum gear ratios ", sum_gear_ratios);
+        report.finish();
+        return;
+    }
 
-fn main() {
-    let mut buffer = String::new();
-    let mut schematic_data: Vec<Vec<char>> = vec![];
+    let schematic_data = input::exit_on_error(parse_input(&mut input));
+    let schematic = input::exit_on_error(EngineSchematic::new(&schematic_data));
 
-    while io::stdin().read_line(&mut buffer).unwrap() != 0 {
-        let line: Vec<char> = buffer.trim().chars().collect();
-        schematic_data.push(line);
-        buffer.clear();
+    if let Some(format) = args.render {
+        print!("{}", render::render(&schematic, format));
+        return;
     }
 
-    let schematic = EngineSchematic::new(&schematic_data);
-    let numbers = schematic.get_numbers();
-    let gear_positions = schematic.get_gears();
-
-    let sum_part_numbers: u128 = numbers
-        .iter()
-        .filter(|number| !schematic.get_surrounding_parts(number).is_empty())
-        .map(|number| number.number as u128)
-        .sum();
+    if let Some(query) = args.query {
+        print_query(&schematic, &query);
+        return;
+    }
 
-    let sum_gear_ratios: u128 = gear_positions
-        .iter()
-        .map(|position| schematic.get_gear_ratio(position))
-        .filter(|ratio| ratio.is_some())
-        .map(|ratio| ratio.unwrap())
-        .sum();
+    report.answer(1, "sum part numbers", input::exit_on_error(part1(&schematic)));
+    report.answer(2, "sum gear ratios ", input::exit_on_error(part2(&schematic)));
+    report.finish();
 
-    println!("sum part numbers = {}", sum_part_numbers);
-    println!("sum gear ratios  = {}", sum_gear_ratios);
+    println!();
+    println!("symbol\tcount\tparts");
+    for (symbol, summary) in schematic.symbol_summary() {
+        println!("{}\t{}\t{}", symbol, summary.occurrences, summary.parts);
+    }
 }
@@ -0,0 +1,272 @@
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::input::read_error;
+use crate::solution::Solution;
+
+use query::Aggregate;
+
+pub mod query;
+pub mod render;
+pub mod stream;
+
+pub fn is_symbol(char: char) -> bool {
+    !char.is_ascii_digit() && char != '.'
+}
+
+/// Parses a run of digits on row `y`, counting from 0, as a part number.
+pub fn parse_number(digits: &str, y: usize) -> Result<u16, String> {
+    digits.parse().map_err(|err| format!("invalid number {} on row {}: {}", digits, y + 1, err))
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Position(pub i32, pub i32);
+
+impl Position {
+    pub fn surrounding(&self, width: usize, height: usize) -> Vec<Self> {
+        let mut positions = vec![];
+
+        let start_x = self.0 - 1;
+        let stop_x = self.0 + width as i32;
+        let start_y = self.1 - 1;
+        let stop_y = self.1 + height as i32;
+
+        for x in start_x..=stop_x {
+            positions.push(Position(x, start_y));
+            positions.push(Position(x, stop_y));
+        }
+
+        for y in (start_y + 1)..stop_y {
+            positions.push(Position(start_x, y));
+            positions.push(Position(stop_x, y));
+        }
+
+        positions
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct EngineSchematicNumber {
+    pub number: u16,
+    pub position: Position,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl EngineSchematicNumber {
+    pub fn new(number_data: &str, last_x: i32, last_y: i32) -> Result<Self, String> {
+        Ok(EngineSchematicNumber {
+            number: parse_number(number_data, last_y as usize)?,
+            position: Position(
+                last_x - number_data.len() as i32,
+                last_y,
+            ),
+            width: number_data.len(),
+            height: 1,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct EngineSchematic<'a> {
+    width: usize,
+    height: usize,
+    schematic: &'a Vec<Vec<char>>,
+    numbers: Vec<EngineSchematicNumber>,
+    labels: Vec<Vec<Option<usize>>>,
+}
+
+impl<'a> EngineSchematic<'a> {
+    /// Finds the numbers in a schematic whose rows all have the same width.
+    pub fn new(schematic: &'a Vec<Vec<char>>) -> Result<Self, String> {
+        if schematic.is_empty() {
+            return Err("empty schematic".to_string());
+        }
+
+        let width = schematic[0].len();
+        let height = schematic.len();
+
+        if let Some((y, row)) = schematic.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(format!("row {} is {} wide, expected {}", y + 1, row.len(), width));
+        }
+
+        let numbers = Self::find_numbers(schematic)?;
+
+        let mut labels: Vec<Vec<Option<usize>>> = schematic.iter()
+            .map(|row| vec![None; row.len()])
+            .collect();
+        for (id, number) in numbers.iter().enumerate() {
+            let Position(x, y) = number.position;
+            for cell in &mut labels[y as usize][x as usize..x as usize + number.width] {
+                *cell = Some(id);
+            }
+        }
+
+        Ok(EngineSchematic {
+            schematic,
+            width,
+            height,
+            numbers,
+            labels,
+        })
+    }
+
+    fn get_char(&self, pos: &Position) -> char {
+        self.schematic[pos.1 as usize][pos.0 as usize]
+    }
+
+    fn is_in_bounds(&self, pos: &Position) -> bool {
+        pos.0 >= 0
+            && pos.0 < self.width as i32
+            && pos.1 >= 0
+            && pos.1 < self.height as i32
+    }
+
+    pub fn get_symbols(&self, symbols: impl Fn(char) -> bool) -> Vec<(Position, char)> {
+        let mut parts = Vec::new();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let position = Position(x as i32, y as i32);
+                let char = self.get_char(&position);
+
+                if is_symbol(char) && symbols(char) {
+                    parts.push((position, char));
+                }
+            }
+        }
+
+        parts
+    }
+
+    fn find_numbers(schematic: &[Vec<char>]) -> Result<Vec<EngineSchematicNumber>, String> {
+        let mut numbers = Vec::new();
+        let mut number = String::new();
+
+        for (y, row) in schematic.iter().enumerate() {
+            for (x, char) in row.iter().enumerate() {
+                if char.is_ascii_digit() {
+                    number.push(*char);
+                } else if !number.is_empty() {
+                    numbers.push(EngineSchematicNumber::new(number.as_ref(), x as i32, y as i32)?);
+                    number.clear();
+                }
+            }
+
+            if !number.is_empty() {
+                numbers.push(EngineSchematicNumber::new(number.as_ref(), row.len() as i32, y as i32)?);
+                number.clear();
+            }
+        }
+
+        Ok(numbers)
+    }
+
+    pub fn get_numbers(&self) -> &[EngineSchematicNumber] {
+        &self.numbers
+    }
+
+    pub fn get_surrounding_parts(&self, number: &EngineSchematicNumber) -> Vec<(Position, char)> {
+        let positions = number.position.surrounding(number.width, number.height);
+
+        positions
+            .iter()
+            .filter(|position| self.is_in_bounds(position))
+            .map(|position| (position, self.get_char(position)))
+            .filter(|(_, char)| is_symbol(*char))
+            .map(|(position, char)| (*position, char))
+            .collect()
+    }
+
+    pub fn get_adjacent_numbers(&self, position: &Position) -> Vec<usize> {
+        let mut ids: Vec<usize> = Vec::with_capacity(2);
+
+        position.surrounding(1, 1)
+            .iter()
+            .filter_map(|position| self.number_at(position))
+            .for_each(|id| if !ids.contains(&id) { ids.push(id) });
+
+        ids
+    }
+
+    pub fn number_at(&self, position: &Position) -> Option<usize> {
+        if self.is_in_bounds(position) {
+            self.labels[position.1 as usize][position.0 as usize]
+        } else {
+            None
+        }
+    }
+
+    pub fn get_number(&self, id: usize) -> &EngineSchematicNumber {
+        &self.numbers[id]
+    }
+}
+
+pub fn parse_input(input: impl BufRead) -> Result<Vec<Vec<char>>, String> {
+    input.lines()
+        .map(|line| line.map(|line| line.trim().chars().collect()).map_err(read_error))
+        .collect()
+}
+
+/// Sum of the numbers next to a symbol.
+pub fn part1(schematic: &EngineSchematic) -> Result<Answer, String> {
+    Ok(schematic.get_numbers()
+        .iter()
+        .filter(|number| !schematic.get_surrounding_parts(number).is_empty())
+        .map(|number| number.number as u128)
+        .sum::<u128>()
+        .into())
+}
+
+/// Sum of the gear ratios: products of the two numbers next to a `*`.
+pub fn part2(schematic: &EngineSchematic) -> Result<Answer, String> {
+    Ok(schematic
+        .query(|symbol| symbol == '*', |count| count == 2)
+        .iter()
+        .filter_map(|gear| gear.aggregate(Aggregate::Product))
+        .sum::<u128>()
+        .into())
+}
+
+pub fn solve(input: impl BufRead) -> Result<Solution, String> {
+    let mut solution = Solution::new(3);
+    let rows = solution.parse(|| parse_input(input))?;
+    let schematic = solution.parse(|| EngineSchematic::new(&rows))?;
+    solution.solve_both(|| part1(&schematic), || part2(&schematic))?;
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&str]) -> Vec<Vec<char>> {
+        rows.iter().map(|row| row.chars().collect()).collect()
+    }
+
+    #[test]
+    fn equal_numbers_on_one_gear_are_both_counted() {
+        for gear in [rows(&[".12*12."]), rows(&["12.", ".*.", "12."])] {
+            let schematic = EngineSchematic::new(&gear).unwrap();
+
+            assert_eq!(part2(&schematic), Ok(Answer::from(144_u128)));
+        }
+    }
+
+    #[test]
+    fn numbers_are_positioned_at_their_first_digit() {
+        let rows = rows(&["467..114..", "...*......", "..35..633."]);
+        let schematic = EngineSchematic::new(&rows).unwrap();
+        let positions: Vec<(u16, Position)> = schematic.get_numbers().iter()
+            .map(|number| (number.number, number.position))
+            .collect();
+
+        assert_eq!(positions, vec![
+            (467, Position(0, 0)),
+            (114, Position(5, 0)),
+            (35, Position(2, 2)),
+            (633, Position(6, 2)),
+        ]);
+        assert_eq!(schematic.get_adjacent_numbers(&Position(3, 1)), vec![0, 2]);
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
+
+use super::{EngineSchematic, EngineSchematicNumber, Position};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Aggregate {
+    Sum,
+    Product,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    pub fn apply(&self, values: impl IntoIterator<Item = u128>) -> Option<u128> {
+        let mut values = values.into_iter();
+        let first = values.next()?;
+
+        Some(values.fold(first, |acc, value| match self {
+            Aggregate::Sum => acc + value,
+            Aggregate::Product => acc * value,
+            Aggregate::Min => acc.min(value),
+            Aggregate::Max => acc.max(value),
+        }))
+    }
+}
+
+impl FromStr for Aggregate {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "sum" => Ok(Aggregate::Sum),
+            "product" => Ok(Aggregate::Product),
+            "min" => Ok(Aggregate::Min),
+            "max" => Ok(Aggregate::Max),
+            _ => Err("expected sum, product, min or max".to_string()),
+        }
+    }
+}
+
+/// A symbol in the schematic together with the distinct numbers adjacent to it.
+#[derive(Debug)]
+pub struct SymbolMatch<'s> {
+    pub position: Position,
+    pub symbol: char,
+    pub numbers: Vec<&'s EngineSchematicNumber>,
+}
+
+impl SymbolMatch<'_> {
+    /// Aggregates the adjacent numbers, `None` when there are none.
+    pub fn aggregate(&self, aggregate: Aggregate) -> Option<u128> {
+        aggregate.apply(self.numbers.iter().map(|number| number.number as u128))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SymbolSummary {
+    /// How often the symbol occurs in the schematic.
+    pub occurrences: usize,
+    /// How many distinct numbers touch at least one occurrence of the symbol.
+    pub parts: usize,
+}
+
+impl EngineSchematic<'_> {
+    /// All symbols accepted by `symbols` whose number of adjacent numbers is accepted by `count`.
+    pub fn query(&self, symbols: impl Fn(char) -> bool, count: impl Fn(usize) -> bool) -> Vec<SymbolMatch<'_>> {
+        self.get_symbols(symbols)
+            .into_iter()
+            .map(|(position, symbol)| SymbolMatch {
+                position,
+                symbol,
+                numbers: self.get_adjacent_numbers(&position)
+                    .into_iter()
+                    .map(|id| self.get_number(id))
+                    .collect(),
+            })
+            .filter(|symbol_match| count(symbol_match.numbers.len()))
+            .collect()
+    }
+
+    pub fn symbol_summary(&self) -> BTreeMap<char, SymbolSummary> {
+        let mut parts: BTreeMap<char, HashSet<usize>> = BTreeMap::new();
+        let mut summary: BTreeMap<char, SymbolSummary> = BTreeMap::new();
+
+        for (position, symbol) in self.get_symbols(|_| true) {
+            summary.entry(symbol).or_default().occurrences += 1;
+            parts.entry(symbol).or_default().extend(self.get_adjacent_numbers(&position));
+        }
+
+        for (symbol, ids) in parts {
+            summary.entry(symbol).or_default().parts = ids.len();
+        }
+
+        summary
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::str::FromStr;
+
+use super::query::Aggregate;
+use super::{EngineSchematic, Position};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RenderFormat {
+    Ansi,
+    Html,
+}
+
+impl FromStr for RenderFormat {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "ansi" => Ok(RenderFormat::Ansi),
+            "html" => Ok(RenderFormat::Html),
+            _ => Err("expected ansi or html".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Highlight {
+    Part,
+    NonPart,
+    Gear,
+    Symbol,
+    Empty,
+}
+
+impl Highlight {
+    fn ansi(&self) -> &'static str {
+        match self {
+            Highlight::Part => "\x1b[32m",
+            Highlight::NonPart => "\x1b[31m",
+            Highlight::Gear => "\x1b[1;33m",
+            Highlight::Symbol => "\x1b[1m",
+            Highlight::Empty => "\x1b[2m",
+        }
+    }
+
+    fn class(&self) -> &'static str {
+        match self {
+            Highlight::Part => "part",
+            Highlight::NonPart => "non-part",
+            Highlight::Gear => "gear",
+            Highlight::Symbol => "symbol",
+            Highlight::Empty => "empty",
+        }
+    }
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+const HTML_HEADER: &str = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Engine schematic</title>
+<style>
+body { background: #1e1e1e; color: #d4d4d4; }
+.part { color: #6a9955; }
+.non-part { color: #f44747; }
+.gear { color: #dcdcaa; font-weight: bold; }
+.symbol { font-weight: bold; }
+.empty { color: #555555; }
+.ratio { color: #dcdcaa; }
+</style>
+</head>
+<body>
+<pre>
+";
+
+const HTML_FOOTER: &str = "</pre>
+</body>
+</html>
+";
+
+/// Renders the schematic with part numbers, non-part numbers and gears highlighted. The ratios of
+/// the gears on a row are listed after that row.
+pub fn render(schematic: &EngineSchematic, format: RenderFormat) -> String {
+    let parts: HashSet<usize> = (0..schematic.get_numbers().len())
+        .filter(|id| !schematic.get_surrounding_parts(schematic.get_number(*id)).is_empty())
+        .collect();
+
+    let gears: HashMap<Position, u128> = schematic
+        .query(|symbol| symbol == '*', |count| count == 2)
+        .into_iter()
+        .filter_map(|gear| gear.aggregate(Aggregate::Product).map(|ratio| (gear.position, ratio)))
+        .collect();
+
+    let highlight = |position: &Position| -> Highlight {
+        let char = schematic.get_char(position);
+
+        if let Some(id) = schematic.number_at(position) {
+            if parts.contains(&id) { Highlight::Part } else { Highlight::NonPart }
+        } else if gears.contains_key(position) {
+            Highlight::Gear
+        } else if char == '.' {
+            Highlight::Empty
+        } else {
+            Highlight::Symbol
+        }
+    };
+
+    let mut output = String::new();
+
+    if format == RenderFormat::Html {
+        output.push_str(HTML_HEADER);
+    }
+
+    for y in 0..schematic.height {
+        let mut runs: Vec<(Highlight, String)> = vec![];
+        let mut ratios = vec![];
+
+        for x in 0..schematic.width {
+            let position = Position(x as i32, y as i32);
+            let highlight = highlight(&position);
+            let char = schematic.get_char(&position);
+
+            match runs.last_mut() {
+                Some((last, run)) if *last == highlight => run.push(char),
+                _ => runs.push((highlight, char.to_string())),
+            }
+
+            if let Some(ratio) = gears.get(&position) {
+                ratios.push(ratio.to_string());
+            }
+        }
+
+        for (highlight, run) in runs {
+            match format {
+                RenderFormat::Ansi => write!(output, "{}{}{}", highlight.ansi(), run, ANSI_RESET),
+                RenderFormat::Html => write!(output, "<span class=\"{}\">{}</span>", highlight.class(), escape_html(&run)),
+            }.unwrap();
+        }
+
+        if !ratios.is_empty() {
+            match format {
+                RenderFormat::Ansi => write!(output, "  {}{}{}", Highlight::Gear.ansi(), ratios.join(" "), ANSI_RESET),
+                RenderFormat::Html => write!(output, "  <span class=\"ratio\">{}</span>", ratios.join(" ")),
+            }.unwrap();
+        }
+
+        output.push('\n');
+    }
+
+    if format == RenderFormat::Html {
+        output.push_str(HTML_FOOTER);
+    }
+
+    output
+}
+
+fn escape_html(str: &str) -> String {
+    str.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,156 @@
+use std::collections::VecDeque;
+
+use super::{is_symbol, parse_number};
+
+/// A part number or gear ratio found while streaming, positioned by row and column.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StreamEvent {
+    PartNumber { row: usize, column: usize, number: u128 },
+    GearRatio { row: usize, column: usize, ratio: u128 },
+}
+
+#[derive(Debug)]
+struct Row {
+    chars: Vec<char>,
+    /// Digit runs in the row as `(start, end, number)` with an exclusive end.
+    numbers: Vec<(usize, usize, u16)>,
+}
+
+impl Row {
+    /// Finds the digit runs of row `index`, which have to be valid part numbers.
+    fn new(index: usize, chars: Vec<char>) -> Result<Self, String> {
+        let mut numbers = vec![];
+        let mut start: Option<usize> = None;
+
+        for x in 0..=chars.len() {
+            let is_digit = x < chars.len() && chars[x].is_ascii_digit();
+
+            match (start, is_digit) {
+                (None, true) => start = Some(x),
+                (Some(from), false) => {
+                    let digits = String::from_iter(&chars[from..x]);
+                    numbers.push((from, x, parse_number(&digits, index)?));
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self { chars, numbers })
+    }
+
+    fn has_symbol(&self, from: usize, to: usize) -> bool {
+        self.chars.iter()
+            .take(to + 1)
+            .skip(from)
+            .any(|char| is_symbol(*char))
+    }
+
+    fn numbers_touching(&self, column: usize) -> impl Iterator<Item = u16> + '_ {
+        self.numbers.iter()
+            .filter(move |(start, end, _)| *start <= column + 1 && column <= *end)
+            .map(|(_, _, number)| *number)
+    }
+}
+
+/// Processes a schematic one line at a time while only keeping three rows in memory. A row's
+/// part numbers and gears are emitted once the row below it has been pushed.
+#[derive(Debug, Default)]
+pub struct SchematicWindow {
+    rows: VecDeque<Row>,
+    processed: usize,
+}
+
+impl SchematicWindow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, line: Vec<char>) -> Result<Vec<StreamEvent>, String> {
+        self.rows.push_back(Row::new(self.processed + self.rows.len(), line)?);
+
+        let events = match self.rows.len() {
+            2 => Self::process(self.processed, None, &self.rows[0], Some(&self.rows[1])),
+            3 => Self::process(self.processed, Some(&self.rows[0]), &self.rows[1], Some(&self.rows[2])),
+            _ => return Ok(vec![]),
+        };
+
+        if self.rows.len() == 3 {
+            self.rows.pop_front();
+        }
+
+        self.processed += 1;
+        Ok(events)
+    }
+
+    /// Emits the events of the last row, after which the window is empty.
+    pub fn finish(&mut self) -> Vec<StreamEvent> {
+        let events = match self.rows.len() {
+            1 => Self::process(self.processed, None, &self.rows[0], None),
+            2 => Self::process(self.processed, Some(&self.rows[0]), &self.rows[1], None),
+            _ => return vec![],
+        };
+
+        self.rows.clear();
+        self.processed += 1;
+        events
+    }
+
+    fn process(row_index: usize, above: Option<&Row>, row: &Row, below: Option<&Row>) -> Vec<StreamEvent> {
+        let neighbours = [above, Some(row), below];
+        let mut events = vec![];
+
+        for (start, end, number) in &row.numbers {
+            let from = start.saturating_sub(1);
+
+            if neighbours.iter().flatten().any(|neighbour| neighbour.has_symbol(from, *end)) {
+                events.push(StreamEvent::PartNumber { row: row_index, column: *start, number: *number as u128 });
+            }
+        }
+
+        for (column, _) in row.chars.iter().enumerate().filter(|(_, char)| **char == '*') {
+            let adjacent: Vec<u16> = neighbours.iter()
+                .flatten()
+                .flat_map(|neighbour| neighbour.numbers_touching(column))
+                .collect();
+
+            if let [first, second] = adjacent[..] {
+                events.push(StreamEvent::GearRatio { row: row_index, column, ratio: first as u128 * second as u128 });
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day03::EngineSchematic;
+
+    use super::*;
+
+    #[test]
+    fn numbers_too_large_are_errors() {
+        let mut window = SchematicWindow::new();
+        let error = "invalid number 123456 on row 2: number too large to fit in target type".to_string();
+
+        assert!(window.push("467..114".chars().collect()).unwrap().is_empty());
+        assert_eq!(window.push("123456*2".chars().collect()).unwrap_err(), error);
+
+        // The same error as without streaming
+        let schematic: Vec<Vec<char>> = ["467..114", "123456*2"].iter().map(|row| row.chars().collect()).collect();
+        assert_eq!(EngineSchematic::new(&schematic).unwrap_err(), error);
+    }
+
+    #[test]
+    fn gear_ratios_of_the_largest_numbers_fit() {
+        let mut window = SchematicWindow::new();
+
+        window.push("65535*65535".chars().collect()).unwrap();
+        assert_eq!(window.finish(), vec![
+            StreamEvent::PartNumber { row: 0, column: 0, number: 65535 },
+            StreamEvent::PartNumber { row: 0, column: 6, number: 65535 },
+            StreamEvent::GearRatio { row: 0, column: 5, ratio: 65535 * 65535 },
+        ]);
+    }
+}
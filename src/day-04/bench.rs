@@ -0,0 +1,29 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use crate::generate;
+
+use super::Card;
+
+/// Generates `cards` cards in the shape of the real input and times parsing and matching them.
+pub fn run(cards: usize) {
+    let input = generate::day04(cards, 0x2023_0004);
+
+    let start = Instant::now();
+    let parsed: Vec<Card> = input.lines()
+        .map(|line| Card::parse(line).unwrap())
+        .collect();
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let matches: usize = parsed.iter()
+        .map(|card| black_box(card).winning_count())
+        .sum();
+    let match_time = start.elapsed();
+
+    println!("cards          = {}", cards);
+    println!("input size     = {} bytes", input.len());
+    println!("parse          = {:?} ({:.1} ns/card)", parse_time, parse_time.as_nanos() as f64 / cards as f64);
+    println!("winning counts = {:?} ({:.1} ns/card)", match_time, match_time.as_nanos() as f64 / cards as f64);
+    println!("total matches  = {}", matches);
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use super::Card;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum CascadeError {
+    Empty,
+    Duplicate(u32),
+    OutOfOrder { previous: u32, card: u32 },
+    Gap { previous: u32, card: u32 },
+    /// More copies than fit in a `u128`.
+    Overflow,
+}
+
+impl Display for CascadeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CascadeError::Empty => write!(f, "no cards"),
+            CascadeError::Duplicate(id) => write!(f, "card {} appears more than once", id),
+            CascadeError::OutOfOrder { previous, card } => write!(f, "card {} comes after card {}", card, previous),
+            CascadeError::Gap { previous, card } if *card == previous + 2 => write!(f, "card {} is missing", previous + 1),
+            CascadeError::Gap { previous, card } => write!(f, "cards {} to {} are missing", previous + 1, card - 1),
+            CascadeError::Overflow => write!(f, "too many copies to count"),
+        }
+    }
+}
+
+/// The result of letting every card win copies of the cards following it, keyed by card id.
+#[derive(Debug)]
+pub struct Cascade {
+    copies: BTreeMap<u32, u128>,
+    /// Cards that would win copies of cards past the end of the table, with how many ids they
+    /// would have reached beyond it.
+    beyond_table: BTreeMap<u32, usize>,
+    total: u128,
+}
+
+impl Cascade {
+    /// Runs the cascade over cards that are ordered by id without gaps.
+    pub fn new(cards: &[Card]) -> Result<Self, CascadeError> {
+        Self::validate(cards)?;
+
+        let mut copies: BTreeMap<u32, u128> = cards.iter()
+            .map(|card| (card.id, 1))
+            .collect();
+        let mut beyond_table = BTreeMap::new();
+        let last = cards.last().unwrap().id as u64;
+
+        for card in cards {
+            let current = copies[&card.id];
+            let reach = card.id as u64 + card.winning_count() as u64;
+
+            for id in (card.id as u64 + 1)..=reach.min(last) {
+                let copies = copies.get_mut(&(id as u32)).unwrap();
+                *copies = copies.checked_add(current).ok_or(CascadeError::Overflow)?;
+            }
+
+            if reach > last {
+                beyond_table.insert(card.id, (reach - last) as usize);
+            }
+        }
+
+        let total = copies.values()
+            .try_fold(0_u128, |total, copies| total.checked_add(*copies))
+            .ok_or(CascadeError::Overflow)?;
+
+        Ok(Self { copies, beyond_table, total })
+    }
+
+    fn validate(cards: &[Card]) -> Result<(), CascadeError> {
+        if cards.is_empty() {
+            return Err(CascadeError::Empty);
+        }
+
+        for (previous, card) in cards.iter().zip(cards.iter().skip(1)) {
+            if card.id == previous.id {
+                return Err(CascadeError::Duplicate(card.id));
+            } else if card.id < previous.id {
+                return Err(CascadeError::OutOfOrder { previous: previous.id, card: card.id });
+            } else if card.id > previous.id + 1 {
+                return Err(CascadeError::Gap { previous: previous.id, card: card.id });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn copies(&self) -> &BTreeMap<u32, u128> {
+        &self.copies
+    }
+
+    pub fn total(&self) -> u128 {
+        self.total
+    }
+
+    pub fn beyond_table(&self) -> &BTreeMap<u32, usize> {
+        &self.beyond_table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cards that each win copies of the next two, so the copies grow like the Fibonacci numbers.
+    fn cards(count: u32) -> Vec<Card> {
+        (1..=count).map(|id| Card::parse(&format!("Card {}: 1 2 | 1 2", id)).unwrap()).collect()
+    }
+
+    #[test]
+    fn too_many_copies_overflow() {
+        assert_eq!(Cascade::new(&cards(180)).unwrap().copies()[&180], 48558529144435440119720805669229197640);
+        assert_eq!(Cascade::new(&cards(200)).unwrap_err(), CascadeError::Overflow);
+    }
+}
@@ -1,82 +1,74 @@
-use std::collections::{HashSet, VecDeque};
-use std::io;
-use regex::Regex;
+use advent_of_code_2023::answers::Report;
+use advent_of_code_2023::day04::cascade::Cascade;
+use advent_of_code_2023::day04::scoring::ScoringRule;
+use advent_of_code_2023::day04::validation::check_cards;
+use advent_of_code_2023::day04::{bench, parse_input, solve_with, total_score};
+use advent_of_code_2023::input;
+use advent_of_code_2023::output::Output;
+use advent_of_code_2023::timings::CountingAllocator;
 
-fn get_number_array(numbers: &str) -> Vec<u32> {
-    numbers.trim().split_whitespace()
-        .filter(|str| !str.is_empty())
-        .map(|str| str.parse().unwrap())
-        .collect()
-}
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
-#[derive(Debug)]
-struct Card {
-    id: u32,
-    winning_numbers: HashSet<u32>,
-    numbers: Vec<u32>,
-}
+fn main() {
+    let (mut input, mut args) = input::open(4);
+    let mut report = Report::new(4, &input.source, &mut args);
 
-impl Card {
-    pub fn parse(line: &str) -> Result<Card, &str> {
-        let re = Regex::new(r"Card \s*(\d+): ([\d\s]+) \| ([\d\s]+)").unwrap();
+    let output = input::exit_on_error(Output::from_args(&mut args));
 
-        if let Some(captures) = re.captures(line) {
-            let winning_numbers = get_number_array(captures.get(2).unwrap().as_str());
+    let bench = args.iter().position(|arg| arg == "--bench");
+    let print_copies = args.iter().any(|arg| arg == "--copies");
+    let strict = args.iter().any(|arg| arg == "--strict");
+    let rule: ScoringRule = input::exit_on_error(input::take_value(&mut args, "--scoring")
+        .and_then(|rule| rule.map_or(Ok(ScoringRule::Doubling), |rule| input::parse_value("--scoring", &rule))));
 
-            Ok(Self {
-                id: captures.get(1).unwrap().as_str().trim().parse().unwrap(),
-                winning_numbers: HashSet::from_iter(winning_numbers),
-                numbers: get_number_array(captures.get(3).unwrap().as_str()),
-            })
-        } else {
-            Err("Could not match")
+    if output.results_only() {
+        for (flag, given) in [("--bench", bench.is_some()), ("--copies", print_copies)] {
+            if given {
+                input::exit_on_error(output.check_flag(flag));
+            }
+        }
+
+        // Only the puzzle's scoring rule has known answers
+        if rule != ScoringRule::Doubling {
+            report.leave_unchecked(1);
         }
-    }
 
-    pub fn winning_count(&self) -> usize {
-        self.numbers.iter()
-            .filter(|num| self.winning_numbers.contains(num))
-            .count()
+        let solution = input::exit_on_error(solve_with(&mut input, &rule, strict));
+        output.print(&report.results(&solution), &[solution]);
+        report.finish();
+        return;
     }
 
-    pub fn score(&self) -> u128 {
-        let count = self.winning_count();
-        if count == 0 {
-            0
-        } else {
-            2_u128.pow((count - 1) as u32)
-        }
+    if let Some(index) = bench {
+        let cards = input::exit_on_error(args.get(index + 1).map_or(Ok(1_000_000), |cards| input::parse_value("--bench", cards)));
+        bench::run(cards);
+        return;
     }
-}
 
-fn main() {
-    let mut buffer = String::new();
-    let mut total_score = 0;
-    let mut next_copies = VecDeque::with_capacity(5);
-    let mut total_copies = 0;
+    let cards = input::exit_on_error(parse_input(&mut input));
+    input::exit_on_error(check_cards(&cards, strict));
 
-    while io::stdin().read_line(&mut buffer).unwrap() != 0 {
-        let card = Card::parse(buffer.trim()).unwrap();
+    let cascade = input::exit_on_error(Cascade::new(&cards).map_err(|err| format!("invalid card table: {}", err)));
 
-        let current_copies = next_copies.pop_front()
-            .or(Some(1))
-            .unwrap();
-        let count = card.winning_count();
+    for (id, beyond) in cascade.beyond_table() {
+        eprintln!("warning: card {} would win copies of {} card(s) past the end of the table", id, beyond);
+    }
 
-        for n in 0..count {
-            if let Some(queue_item) = next_copies.get_mut(n) {
-                *queue_item += current_copies;
-            } else {
-                next_copies.push_back(current_copies + 1);
-            }
+    if print_copies {
+        for (id, copies) in cascade.copies() {
+            println!("card {:>3} copies = {}", id, copies);
         }
+    }
 
-        total_copies += current_copies;
-        total_score += card.score();
+    let total_score = input::exit_on_error(total_score(&cards, &rule));
 
-        buffer.clear();
+    // Only the puzzle's scoring rule has known answers
+    if rule == ScoringRule::Doubling {
+        report.answer(1, "total score", total_score);
+    } else {
+        println!("total score = {}", total_score);
     }
-
-    println!("total score = {}", total_score);
-    println!("total cards = {}", total_copies);
+    report.answer(2, "total cards", cascade.total());
+    report.finish();
 }
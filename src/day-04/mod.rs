@@ -0,0 +1,154 @@
+use std::fmt::{Display, Formatter};
+use std::num::ParseIntError;
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::input::read_error;
+use crate::solution::Solution;
+
+use cascade::Cascade;
+use numbers::NumberSet;
+use scoring::ScoringRule;
+use CardError::{InvalidCardId, InvalidNumber, NoCardId, NoSeparator};
+
+pub mod bench;
+pub mod cascade;
+pub mod numbers;
+pub mod scoring;
+pub mod validation;
+
+#[derive(Debug)]
+pub enum CardError {
+    NoCardId,
+    InvalidCardId(ParseIntError),
+    NoSeparator,
+    InvalidNumber(String, ParseIntError),
+}
+
+impl Display for CardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NoCardId => write!(f, "no card id"),
+            InvalidCardId(err) => write!(f, "invalid card id: {}", err),
+            NoSeparator => write!(f, "no '|' between the winning numbers and the numbers"),
+            InvalidNumber(number, err) => write!(f, "invalid number {}: {}", number, err),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Card {
+    pub id: u32,
+    winning_numbers: NumberSet,
+    numbers: NumberSet,
+    duplicate_winning_numbers: NumberSet,
+    duplicate_numbers: NumberSet,
+    /// Every occurrence of a number you have after its first, each of which can match again.
+    repeated_numbers: Vec<u32>,
+}
+
+impl Card {
+    pub fn parse(line: &str) -> Result<Card, CardError> {
+        let (id, numbers) = line.strip_prefix("Card")
+            .and_then(|rest| rest.split_once(':'))
+            .ok_or(NoCardId)?;
+        let (winning_numbers, numbers) = numbers.split_once('|')
+            .ok_or(NoSeparator)?;
+
+        let (winning_numbers, repeated_winning_numbers) = NumberSet::parse(winning_numbers)
+            .map_err(|(number, err)| InvalidNumber(number, err))?;
+        let (numbers, repeated_numbers) = NumberSet::parse(numbers)
+            .map_err(|(number, err)| InvalidNumber(number, err))?;
+
+        Ok(Self {
+            id: id.trim().parse().map_err(InvalidCardId)?,
+            winning_numbers,
+            numbers,
+            duplicate_winning_numbers: repeated_winning_numbers.into_iter().collect(),
+            duplicate_numbers: repeated_numbers.iter().copied().collect(),
+            repeated_numbers,
+        })
+    }
+
+    /// How many of the numbers you have are winning numbers, counting a number every time it
+    /// occurs.
+    pub fn winning_count(&self) -> usize {
+        let repeated_matches = self.repeated_numbers.iter()
+            .filter(|number| self.winning_numbers.contains(**number))
+            .count();
+
+        self.numbers.intersection(&self.winning_numbers).len() + repeated_matches
+    }
+
+    pub fn score(&self, rule: &ScoringRule) -> Option<u128> {
+        rule.score(self.winning_count())
+    }
+}
+
+pub fn parse_input(input: impl BufRead) -> Result<Vec<Card>, String> {
+    input.lines()
+        .enumerate()
+        .map(|(index, line)| Card::parse(line.map_err(read_error)?.trim())
+            .map_err(|err| format!("invalid card on line {}: {}", index + 1, err)))
+        .collect()
+}
+
+pub fn total_score(cards: &[Card], rule: &ScoringRule) -> Result<u128, String> {
+    cards.iter()
+        .try_fold(0_u128, |total, card| card.score(rule).and_then(|score| total.checked_add(score)))
+        .ok_or_else(|| "total score doesn't fit in 128 bits".to_string())
+}
+
+/// Total score with the doubling rule.
+pub fn part1(cards: &[Card]) -> Result<Answer, String> {
+    total_score(cards, &ScoringRule::Doubling).map(Answer::from)
+}
+
+/// Number of cards after winning copies.
+pub fn part2(cards: &[Card]) -> Result<Answer, String> {
+    Cascade::new(cards)
+        .map(|cascade| cascade.total().into())
+        .map_err(|err| format!("invalid card table: {}", err))
+}
+
+/// Solves both parts, checking the cards first, see [`validation::check_cards`].
+pub fn solve(input: impl BufRead) -> Result<Solution, String> {
+    solve_with(input, &ScoringRule::Doubling, false)
+}
+
+/// Solves both parts like [`solve`], scoring part 1 with `rule` and failing on warnings about the
+/// cards when `strict`.
+pub fn solve_with(input: impl BufRead, rule: &ScoringRule, strict: bool) -> Result<Solution, String> {
+    let mut solution = Solution::new(4);
+    let cards = solution.parse(|| parse_input(input))?;
+    validation::check_cards(&cards, strict)?;
+    solution.solve_both(|| total_score(&cards, rule).map(Answer::from), || part2(&cards))?;
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_numbers_match_every_time() {
+        let card = Card::parse("Card 1: 41 48 300 | 48 48 300 7 48").unwrap();
+
+        assert_eq!(card.winning_count(), 4);
+    }
+
+    #[test]
+    fn repeated_matches_only_fail_strict_checks() {
+        let cards = [Card::parse("Card 1: 41 48 | 48 48 7").unwrap()];
+
+        assert_eq!(validation::check_cards(&cards, false), Ok(()));
+        assert_eq!(validation::check_cards(&cards, true), Err("invalid card 1: matching number 48 occurs more than once".to_string()));
+    }
+
+    #[test]
+    fn invalid_numbers_are_described() {
+        let err = Card::parse("Card 1: 41 4x | 48").unwrap_err();
+
+        assert_eq!(err.to_string(), "invalid number 4x: invalid digit found in string");
+    }
+}
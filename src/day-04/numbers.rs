@@ -0,0 +1,123 @@
+use std::num::ParseIntError;
+
+/// A set of numbers. The numbers 0 to 255, which are all the puzzle uses, are stored as a
+/// fixed-size bitset; larger numbers are kept in a sorted list, which stays empty (and doesn't
+/// allocate) for puzzle inputs.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct NumberSet {
+    small: [u128; 2],
+    large: Vec<u32>,
+}
+
+impl NumberSet {
+    /// Parses whitespace separated numbers, returning the set and every occurrence of a number
+    /// after its first, in order. On an invalid number, returns it with the parse error.
+    pub fn parse(numbers: &str) -> Result<(Self, Vec<u32>), (String, ParseIntError)> {
+        let mut set = Self::default();
+        let mut repeats = vec![];
+
+        for number in numbers.split_ascii_whitespace() {
+            let value = number.parse().map_err(|err| (number.to_string(), err))?;
+
+            if !set.insert(value) {
+                repeats.push(value);
+            }
+        }
+
+        Ok((set, repeats))
+    }
+
+    /// Adds the number, returning whether it was not in the set yet.
+    pub fn insert(&mut self, number: u32) -> bool {
+        match u8::try_from(number) {
+            Ok(small) => {
+                let was_new = !self.contains(number);
+                self.small[(small >> 7) as usize] |= 1 << (small & 127);
+                was_new
+            }
+            Err(_) => match self.large.binary_search(&number) {
+                Ok(_) => false,
+                Err(index) => {
+                    self.large.insert(index, number);
+                    true
+                }
+            },
+        }
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        match u8::try_from(number) {
+            Ok(small) => self.small[(small >> 7) as usize] & (1 << (small & 127)) != 0,
+            Err(_) => self.large.binary_search(&number).is_ok(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.small.iter().map(|word| word.count_ones() as usize).sum::<usize>() + self.large.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.small == [0, 0] && self.large.is_empty()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            small: [self.small[0] & other.small[0], self.small[1] & other.small[1]],
+            large: self.large.iter().copied().filter(|number| other.contains(*number)).collect(),
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = Self {
+            small: [self.small[0] | other.small[0], self.small[1] | other.small[1]],
+            large: self.large.clone(),
+        };
+
+        for number in &other.large {
+            union.insert(*number);
+        }
+
+        union
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..=u8::MAX as u32)
+            .filter(|number| self.contains(*number))
+            .chain(self.large.iter().copied())
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(numbers: I) -> Self {
+        let mut set = Self::default();
+
+        for number in numbers {
+            set.insert(number);
+        }
+
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_past_the_bitset_are_kept() {
+        let (set, repeats) = NumberSet::parse("7 255 256 70000 7 70000").unwrap();
+        let other = NumberSet::from_iter([255, 300, 70000]);
+
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![7, 255, 256, 70000]);
+        assert_eq!(repeats, vec![7, 70000]);
+        assert_eq!(set.intersection(&other).iter().collect::<Vec<_>>(), vec![255, 70000]);
+        assert_eq!(set.union(&other).iter().collect::<Vec<_>>(), vec![7, 255, 256, 300, 70000]);
+        assert_eq!(set.union(&other).len(), 5);
+    }
+
+    #[test]
+    fn invalid_numbers_are_named() {
+        assert_eq!(NumberSet::parse("1 x2").unwrap_err().0, "x2");
+        assert_eq!(NumberSet::parse("4294967296").unwrap_err().0, "4294967296");
+    }
+}
@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+/// How many points a card is worth for its number of matches.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ScoringRule {
+    /// One point per match.
+    Linear,
+    /// One point for the first match, doubled for every match after it.
+    Doubling,
+    /// Points looked up by number of matches, starting at zero matches. Counts past the end of
+    /// the table score its last entry.
+    Table(Vec<u128>),
+}
+
+impl ScoringRule {
+    /// The points for `count` matches, or `None` when they don't fit in a `u128`.
+    pub fn score(&self, count: usize) -> Option<u128> {
+        match self {
+            ScoringRule::Linear => Some(count as u128),
+            ScoringRule::Doubling if count == 0 => Some(0),
+            ScoringRule::Doubling => 2_u128.checked_pow((count - 1) as u32),
+            ScoringRule::Table(table) => Some(table.get(count)
+                .or(table.last())
+                .copied()
+                .unwrap_or(0)),
+        }
+    }
+}
+
+impl FromStr for ScoringRule {
+    type Err = String;
+
+    /// Parses `linear`, `doubling` or `table:<points>,<points>,...`.
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "linear" => Ok(ScoringRule::Linear),
+            "doubling" => Ok(ScoringRule::Doubling),
+            _ => str.strip_prefix("table:")
+                .ok_or("expected linear, doubling or table:<points>,<points>,...")?
+                .split(',')
+                .map(|points| points.trim().parse().map_err(|err| format!("invalid points {}: {}", points, err)))
+                .collect::<Result<Vec<u128>, String>>()
+                .map(ScoringRule::Table),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubling_stops_at_128_bits() {
+        assert_eq!(ScoringRule::Doubling.score(128), Some(1 << 127));
+        assert_eq!(ScoringRule::Doubling.score(129), None);
+    }
+}
@@ -0,0 +1,73 @@
+use std::fmt::{Display, Formatter};
+
+use super::Card;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CardIssue {
+    /// The number occurs more than once in the winning numbers.
+    DuplicateWinningNumber(u32),
+    /// The number occurs more than once in the numbers you have.
+    DuplicateNumber(u32),
+    /// The number is on both sides and occurs more than once on at least one of them. Every
+    /// occurrence of it you have counts as a match.
+    RepeatedMatch(u32),
+}
+
+impl CardIssue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            CardIssue::DuplicateWinningNumber(_) | CardIssue::DuplicateNumber(_) | CardIssue::RepeatedMatch(_) => Severity::Warning,
+        }
+    }
+}
+
+impl Display for CardIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardIssue::DuplicateWinningNumber(number) => write!(f, "winning number {} occurs more than once", number),
+            CardIssue::DuplicateNumber(number) => write!(f, "number {} occurs more than once", number),
+            CardIssue::RepeatedMatch(number) => write!(f, "matching number {} occurs more than once", number),
+        }
+    }
+}
+
+impl Card {
+    pub fn validate(&self) -> Vec<CardIssue> {
+        let matches = self.numbers.intersection(&self.winning_numbers);
+        let repeated_matches = matches.intersection(&self.duplicate_winning_numbers.union(&self.duplicate_numbers));
+
+        let winning = self.duplicate_winning_numbers.iter()
+            .filter(|number| !repeated_matches.contains(*number))
+            .map(CardIssue::DuplicateWinningNumber);
+        let numbers = self.duplicate_numbers.iter()
+            .filter(|number| !repeated_matches.contains(*number))
+            .map(CardIssue::DuplicateNumber);
+
+        winning
+            .chain(numbers)
+            .chain(repeated_matches.iter().map(CardIssue::RepeatedMatch))
+            .collect()
+    }
+}
+
+/// Checks every card, printing warnings. Fails on the first error, or with `strict` on the first
+/// warning as well.
+pub fn check_cards(cards: &[Card], strict: bool) -> Result<(), String> {
+    for card in cards {
+        for issue in card.validate() {
+            if strict || issue.severity() == Severity::Error {
+                return Err(format!("invalid card {}: {}", card.id, issue));
+            }
+
+            eprintln!("warning: card {}: {}", card.id, issue);
+        }
+    }
+
+    Ok(())
+}
@@ -1,92 +1,146 @@
-use std::collections::BTreeSet;
-use std::io;
+use advent_of_code_2023::answers::Report;
+use advent_of_code_2023::day05::seeds::seeds::SeedMaps;
+use advent_of_code_2023::day05::validation::check_almanac;
+use advent_of_code_2023::day05::{min_location, min_range_location, parse_input, reference, solve_with};
+use advent_of_code_2023::input;
+use advent_of_code_2023::input::InputError;
+use advent_of_code_2023::output::Output;
+use advent_of_code_2023::timings::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Debug)]
+struct Args {
+    validate: bool,
+    brute_force: bool,
+    from: String,
+    to: String,
+    value: Option<u64>,
+}
+
+fn parse_args(args: Vec<String>) -> Result<Args, InputError> {
+    let mut parsed = Args {
+        validate: false,
+        brute_force: false,
+        from: "seed".to_string(),
+        to: "location".to_string(),
+        value: None,
+    };
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--validate" {
+            parsed.validate = true;
+            continue;
+        }
+
+        if arg == "--brute-force" {
+            parsed.brute_force = true;
+            continue;
+        }
+
+        let value = args.next()
+            .ok_or_else(|| InputError::InvalidArgument(format!("missing value for {}", arg)))?;
 
-use regex::Regex;
+        match arg.as_str() {
+            "--from" => parsed.from = value,
+            "--to" => parsed.to = value,
+            "--value" => parsed.value = Some(input::parse_value(&arg, &value)?),
+            _ => return Err(InputError::InvalidArgument(format!("unknown argument {}", arg))),
+        }
+    }
+
+    Ok(parsed)
+}
 
-use crate::seeds::seeds::{SeedMapping, SeedMaps, SeedRange};
+/// Prints every conversion from `from` to `to`, including which mapping was used.
+fn print_trace(seed_maps: &SeedMaps, value: u64, path: &[String]) -> Result<(), String> {
+    let steps = seed_maps.trace(value, path).map_err(|err| err.to_string())?;
 
-mod seeds;
+    println!("{} {}", path[0], value);
 
-fn parse_seeds(line: &str) -> (Vec<u32>, Vec<SeedRange>) {
-    let new_seeds: Vec<u32> = line.split_whitespace()
-        .map(|str| str.parse().unwrap())
-        .collect();
-    let mut seed_pairs = vec![];
+    for step in steps {
+        let via = match step.mapping {
+            Some(mapping) => format!("mapping {} {} {}", mapping.destination(), mapping.source(), mapping.range()),
+            None => "identity".to_string(),
+        };
 
-    for i in (0..new_seeds.len()).step_by(2) {
-        seed_pairs.push(SeedRange::new(new_seeds[i], new_seeds[i + 1]));
+        println!("  -> {} {}\t({})", step.value_type, step.converted, via);
     }
 
-    (new_seeds, seed_pairs)
+    Ok(())
 }
 
-fn parse_input() -> (Vec<u32>, Vec<SeedRange>, SeedMaps) {
-    let re = Regex::new(r"(\w+)-to-(\w+) map:").unwrap();
+fn main() {
+    let (mut input, mut args) = input::open(5);
+    let mut report = Report::new(5, &input.source, &mut args);
 
-    let mut buffer = String::new();
-    let mut seeds: Vec<u32> = vec![];
-    let mut seed_pairs: Vec<SeedRange> = vec![];
-    let mut seed_maps = SeedMaps::new();
+    let output = input::exit_on_error(Output::from_args(&mut args));
+    let args = input::exit_on_error(parse_args(args));
 
-    let mut current_map_key: Option<String> = None;
-    let mut start_of_section = true;
+    if output.results_only() {
+        // Tracing and brute forcing print their own output
+        let flags = [("--value", args.value.is_some()), ("--brute-force", args.brute_force)];
 
-    while io::stdin().read_line(&mut buffer).unwrap() != 0 {
-        if !start_of_section && buffer.trim().is_empty() {
-            start_of_section = true;
-            buffer.clear();
-            continue;
+        for (flag, given) in flags {
+            if given {
+                input::exit_on_error(output.check_flag(flag));
+            }
+        }
+
+        let solution = input::exit_on_error(solve_with(&mut input, args.validate));
+        output.print(&report.results(&solution), &[solution]);
+        report.finish();
+        return;
+    }
+
+    let almanac = input::exit_on_error(parse_input(&mut input));
+
+    if let Some(value) = args.value {
+        let paths = input::exit_on_error(almanac.seed_maps.paths(&args.from, &args.to)
+            .map_err(|err| err.to_string()));
+
+        if paths.is_empty() {
+            input::exit_on_error(Err(format!("no path from {} to {}", args.from, args.to)))
         }
 
-        if start_of_section {
-            if buffer.starts_with("seeds: ") {
-                let (singles, pairs) = parse_seeds(buffer.strip_prefix("seeds: ").unwrap());
-                seeds.extend(singles);
-                seed_pairs.extend(pairs);
-            } else {
-                let captures = re.captures(buffer.as_str()).unwrap();
+        for path in paths {
+            input::exit_on_error(print_trace(&almanac.seed_maps, value, &path));
+        }
 
-                let source = captures.get(1).unwrap().as_str();
-                let destination = captures.get(2).unwrap().as_str();
+        return;
+    }
 
-                let key = seed_maps.new_map(source, destination);
+    input::exit_on_error(check_almanac(&almanac.seed_maps, args.validate));
 
-                current_map_key = Some(key);
-            }
+    let paths = input::exit_on_error(almanac.seed_maps.paths("seed", "location")
+        .map_err(|err| err.to_string()));
 
-            start_of_section = false;
+    for path in &paths {
+        let min_location = input::exit_on_error(min_location(&almanac, path));
+        let min_range_location = input::exit_on_error(min_range_location(&almanac, path));
+
+        // Only name the path when there is more than one way to get to a location
+        let via = if paths.len() > 1 { format!(" via {}", path.join(" -> ")) } else { String::new() };
+
+        // Answers along other paths than the puzzle's single one are just printed
+        if paths.len() == 1 {
+            report.answer(1, "single seed min location", min_location);
+            report.answer(2, "ranges seed min location", min_range_location);
         } else {
-            if let Some(map_key) = &current_map_key {
-                seed_maps.get_mut_map(map_key)
-                    .add_mapping(SeedMapping::parse(buffer.as_str()));
-            } else {
-                let (singles, pairs) = parse_seeds(buffer.as_str());
-                seeds.extend(singles);
-                seed_pairs.extend(pairs);
-            }
+            println!("single seed min location{} = {}", via, min_location);
+            println!("ranges seed min location{} = {}", via, min_range_location);
         }
 
-        buffer.clear();
-    }
+        if args.brute_force {
+            let brute_force = input::exit_on_error(reference::min_value_for_ranges(&almanac.seed_maps, &almanac.seed_ranges, path)
+                .map_err(|err| err.to_string())
+                .and_then(|min| min.ok_or_else(|| "no seed ranges".to_string())));
 
-    (seeds, seed_pairs, seed_maps)
-}
+            println!("brute force min location{} = {}", via, brute_force);
+        }
+    }
 
-fn main() {
-    let (seeds, seed_pairs, seed_maps) = parse_input();
-
-    let min_location: u32 = seeds.iter()
-        .map(|seed| seed_maps.get_value(*seed, "seed", "location").unwrap())
-        .min()
-        .unwrap();
-
-    let ranges = seed_maps.get_value_for_ranges(
-        BTreeSet::from_iter(seed_pairs),
-        "seed",
-        "location"
-    )
-        .unwrap();
-
-    println!("single seed min location = {}", min_location);
-    println!("ranges seed min location = {}", ranges.first().unwrap().lowest());
+    report.finish();
 }
@@ -0,0 +1,164 @@
+use std::collections::BTreeSet;
+use std::io::BufRead;
+
+use regex::Regex;
+
+use crate::answer::Answer;
+use crate::input::read_error;
+use crate::solution::Solution;
+
+use seeds::seeds::{MapKey, SeedMapping, SeedMaps, SeedRange};
+
+pub mod reference;
+pub mod seeds;
+pub mod validation;
+
+/// The seeds to plant, read both as single seeds and as ranges, and the maps between categories.
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub seed_ranges: Vec<SeedRange>,
+    pub seed_maps: SeedMaps,
+}
+
+fn parse_seeds(line: &str) -> Result<(Vec<u64>, Vec<SeedRange>), String> {
+    let new_seeds: Vec<u64> = line.split_whitespace()
+        .map(|str| str.parse().map_err(|err| format!("invalid seed {}: {}", str, err)))
+        .collect::<Result<_, _>>()?;
+    let mut seed_pairs = vec![];
+
+    for pair in new_seeds.chunks(2) {
+        let &[start, range] = pair else {
+            return Err(format!("seed {} has no range length", pair[0]));
+        };
+        let seed_range = SeedRange::new(start, range);
+
+        if !seed_range.fits() {
+            return Err(format!("seed range {} {} is too large", start, range));
+        }
+
+        seed_pairs.push(seed_range);
+    }
+
+    Ok((new_seeds, seed_pairs))
+}
+
+pub fn parse_input(mut input: impl BufRead) -> Result<Almanac, String> {
+    let re = Regex::new(r"(\w+)-to-(\w+) map:").unwrap();
+
+    let mut buffer = String::new();
+    let mut line = 0;
+    let mut seeds: Vec<u64> = vec![];
+    let mut seed_pairs: Vec<SeedRange> = vec![];
+    let mut seed_maps = SeedMaps::new();
+
+    let mut current_map_key: Option<MapKey> = None;
+    let mut start_of_section = true;
+
+    while input.read_line(&mut buffer).map_err(read_error)? != 0 {
+        line += 1;
+
+        if !start_of_section && buffer.trim().is_empty() {
+            start_of_section = true;
+            buffer.clear();
+            continue;
+        }
+
+        if start_of_section {
+            if let Some(line_seeds) = buffer.strip_prefix("seeds: ") {
+                let (singles, pairs) = parse_seeds(line_seeds)
+                    .map_err(|err| format!("invalid seeds on line {}: {}", line, err))?;
+                seeds.extend(singles);
+                seed_pairs.extend(pairs);
+            } else {
+                let captures = re.captures(buffer.as_str())
+                    .ok_or_else(|| format!("expected seeds or a map on line {}", line))?;
+
+                let source = captures.get(1).unwrap().as_str();
+                let destination = captures.get(2).unwrap().as_str();
+
+                let key = seed_maps.new_map(source, destination);
+
+                current_map_key = Some(key);
+            }
+
+            start_of_section = false;
+        } else {
+            if let Some(map_key) = &current_map_key {
+                let mapping = SeedMapping::parse(buffer.as_str())
+                    .map_err(|err| format!("invalid mapping on line {}: {}", line, err))?;
+                seed_maps.get_mut_map(map_key).add_mapping(mapping);
+            } else {
+                let (singles, pairs) = parse_seeds(buffer.as_str())
+                    .map_err(|err| format!("invalid seeds on line {}: {}", line, err))?;
+                seeds.extend(singles);
+                seed_pairs.extend(pairs);
+            }
+        }
+
+        buffer.clear();
+    }
+
+    Ok(Almanac { seeds, seed_ranges: seed_pairs, seed_maps })
+}
+
+/// Lowest location of the single seeds along `path`.
+pub fn min_location(almanac: &Almanac, path: &[String]) -> Result<u64, String> {
+    almanac.seeds.iter()
+        .map(|seed| almanac.seed_maps.get_value(*seed, path))
+        .collect::<Result<Vec<u64>, _>>()
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .min()
+        .ok_or_else(|| "no seeds".to_string())
+}
+
+/// Lowest location of the seed ranges along `path`.
+pub fn min_range_location(almanac: &Almanac, path: &[String]) -> Result<u64, String> {
+    almanac.seed_maps.get_value_for_ranges(BTreeSet::from_iter(almanac.seed_ranges.iter().copied()), path)
+        .map_err(|err| err.to_string())?
+        .first()
+        .map(SeedRange::lowest)
+        .ok_or_else(|| "no seed ranges".to_string())
+}
+
+/// The paths from seed to location, at least one.
+fn seed_to_location(almanac: &Almanac) -> Result<Vec<Vec<String>>, String> {
+    let paths = almanac.seed_maps.paths("seed", "location").map_err(|err| err.to_string())?;
+
+    if paths.is_empty() {
+        return Err("no path from seed to location".to_string());
+    }
+
+    Ok(paths)
+}
+
+/// Lowest location of the single seeds, over every path from seed to location.
+pub fn part1(almanac: &Almanac) -> Result<Answer, String> {
+    seed_to_location(almanac)?.iter()
+        .map(|path| min_location(almanac, path))
+        .collect::<Result<Vec<u64>, String>>()
+        .map(|locations| locations.into_iter().min().unwrap().into())
+}
+
+/// Lowest location of the seed ranges, over every path from seed to location.
+pub fn part2(almanac: &Almanac) -> Result<Answer, String> {
+    seed_to_location(almanac)?.iter()
+        .map(|path| min_range_location(almanac, path))
+        .collect::<Result<Vec<u64>, String>>()
+        .map(|locations| locations.into_iter().min().unwrap().into())
+}
+
+/// Solves both parts, checking the almanac first, see [`validation::check_almanac`].
+pub fn solve(input: impl BufRead) -> Result<Solution, String> {
+    solve_with(input, false)
+}
+
+/// Solves both parts like [`solve`], printing the almanac's warnings when `verbose`.
+pub fn solve_with(input: impl BufRead, verbose: bool) -> Result<Solution, String> {
+    let mut solution = Solution::new(5);
+    let almanac = solution.parse(|| parse_input(input))?;
+    validation::check_almanac(&almanac.seed_maps, verbose)?;
+    solution.solve_both(|| part1(&almanac), || part2(&almanac))?;
+    Ok(solution)
+}
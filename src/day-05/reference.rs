@@ -0,0 +1,76 @@
+use super::seeds::seeds::{SeedError, SeedMaps, SeedRange, SeedValue};
+
+/// Finds the lowest value reached along `path` by converting every value in the ranges one at a
+/// time. Far too slow for real inputs, but straightforward enough to check
+/// `SeedMaps::get_value_for_ranges` against.
+pub fn min_value_for_ranges<T: SeedValue>(seed_maps: &SeedMaps<T>, ranges: &[SeedRange<T>], path: &[String]) -> Result<Option<T>, SeedError<T>> {
+    let mut min: Option<T> = None;
+
+    for value in ranges.iter().flat_map(|range| range.values()) {
+        let converted = seed_maps.get_value(value, path)?;
+        min = Some(min.map_or(converted, |min| min.min(converted)));
+    }
+
+    Ok(min)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::day05::seeds::seeds::{SeedMapping, SeedMaps, SeedRange};
+    use crate::generate::Generator;
+
+    use super::min_value_for_ranges;
+
+    const CATEGORIES: [&str; 5] = ["seed", "soil", "water", "light", "location"];
+
+    /// Builds an almanac over `CATEGORIES` where every category maps to one or two later ones.
+    /// The mappings of a map never overlap.
+    fn generate_almanac(generator: &mut Generator) -> SeedMaps<u32> {
+        let mut seed_maps = SeedMaps::new();
+
+        for (index, source) in CATEGORIES.iter().enumerate().take(CATEGORIES.len() - 1) {
+            let mut destinations = vec![CATEGORIES[index + 1]];
+            if index + 2 < CATEGORIES.len() && generator.range(0, 2) == 0 {
+                destinations.push(CATEGORIES[index + 2 + generator.range(0, (CATEGORIES.len() - index - 2) as u64) as usize]);
+            }
+
+            for destination in destinations {
+                let key = seed_maps.new_map(source, destination);
+                let mut start = generator.range(0, 10);
+
+                for _ in 0..generator.range(0, 5) {
+                    let range = generator.range(1, 21);
+                    let mapping = format!("{} {} {}", generator.range(0, 200), start, range);
+
+                    seed_maps.get_mut_map(&key).add_mapping(SeedMapping::parse(&mapping).unwrap());
+                    start += range + generator.range(0, 10);
+                }
+            }
+        }
+
+        seed_maps
+    }
+
+    #[test]
+    fn ranges_match_brute_force() {
+        for seed in 1..=500 {
+            let mut generator = Generator::new(seed);
+            let seed_maps = generate_almanac(&mut generator);
+            let ranges: Vec<SeedRange<u32>> = (0..generator.range(1, 5))
+                .map(|_| SeedRange::new(generator.range(0, 120) as u32, generator.range(1, 41) as u32))
+                .collect();
+
+            for path in seed_maps.paths("seed", "location").unwrap() {
+                let expected = min_value_for_ranges(&seed_maps, &ranges, &path).unwrap();
+                let actual = seed_maps.get_value_for_ranges(BTreeSet::from_iter(ranges.iter().copied()), &path)
+                    .unwrap()
+                    .first()
+                    .map(|range| range.lowest());
+
+                assert_eq!(actual, expected, "seed {} path {:?} ranges {:?}", seed, path, ranges);
+            }
+        }
+    }
+}
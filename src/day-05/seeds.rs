@@ -1,65 +1,142 @@
+#[allow(clippy::module_inception)]
 pub mod seeds {
     use std::cmp::Ordering;
     use std::collections::{BTreeSet, HashMap};
+    use std::fmt::{Debug, Display, Formatter};
+    use std::hash::Hash;
+    use std::str::FromStr;
 
     use itertools::Itertools;
 
+    /// An unsigned integer type that almanac values can be stored in. Arithmetic is done in
+    /// `u128` and checked when converting back.
+    pub trait SeedValue: Copy + Ord + Hash + Debug + Display + FromStr {
+        fn to_wide(self) -> u128;
+
+        fn from_wide(value: u128) -> Option<Self>;
+    }
+
+    macro_rules! impl_seed_value {
+        ($($type:ty),*) => {
+            $(
+                impl SeedValue for $type {
+                    fn to_wide(self) -> u128 {
+                        self as u128
+                    }
+
+                    fn from_wide(value: u128) -> Option<Self> {
+                        Self::try_from(value).ok()
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_seed_value!(u8, u16, u32, u64);
+
+    /// A value that no longer fits the value type after applying a mapping.
+    #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+    pub struct Overflow<T: SeedValue> {
+        pub value: T,
+        pub mapping: SeedMapping<T>,
+    }
+
+    impl<T: SeedValue> Display for Overflow<T> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "mapping {} from {} to {} overflows",
+                self.value,
+                self.mapping.source,
+                self.mapping.destination,
+            )
+        }
+    }
+
+    /// The parts of a range to the left of, inside and to the right of a mapping.
+    pub type Overlap<T> = (Option<SeedRange<T>>, Option<SeedRange<T>>, Option<SeedRange<T>>);
+
     #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
-    pub struct SeedMapping {
-        source: u32,
-        destination: u32,
-        range: u32,
+    pub struct SeedMapping<T: SeedValue = u64> {
+        source: T,
+        destination: T,
+        range: T,
     }
 
-    impl SeedMapping {
-        pub fn parse(string: &str) -> Self {
+    impl<T: SeedValue> SeedMapping<T> {
+        /// Parses `<destination> <source> <range>`, where the last source value has to fit the
+        /// value type.
+        pub fn parse(string: &str) -> Result<Self, String> {
             let (destination, source, range) = string.split_whitespace()
-                .map(|str| str.parse().unwrap())
-                .collect_tuple::<(u32, u32, u32)>()
-                .unwrap();
-            Self { source, destination, range }
+                .map(|str| str.parse().map_err(|_| format!("invalid number {}", str)))
+                .collect::<Result<Vec<T>, String>>()?
+                .into_iter()
+                .collect_tuple::<(T, T, T)>()
+                .ok_or_else(|| "expected a destination, a source and a range".to_string())?;
+
+            if !SeedRange::new(source, range).fits() {
+                return Err(format!("source range {} {} is too large", source, range));
+            }
+
+            Ok(Self { source, destination, range })
+        }
+
+        pub fn source(&self) -> T {
+            self.source
+        }
+
+        pub fn destination(&self) -> T {
+            self.destination
+        }
+
+        pub fn range(&self) -> T {
+            self.range
         }
 
         pub fn end(&self) -> u128 {
-            self.source as u128 + self.range as u128
+            self.source.to_wide() + self.range.to_wide()
         }
 
-        pub fn in_range(&self, value: u32) -> bool {
-            value >= self.source && (value as u128) < self.end()
+        pub fn in_range(&self, value: T) -> bool {
+            value >= self.source && value.to_wide() < self.end()
         }
 
-        pub fn convert(&self, value: u32) -> u32 {
-            return (value as i128 - self.source as i128 + self.destination as i128) as u32;
+        pub fn convert(&self, value: T) -> Result<T, Overflow<T>> {
+            let converted = value.to_wide() - self.source.to_wide() + self.destination.to_wide();
+
+            T::from_wide(converted).ok_or(Overflow { value, mapping: *self })
         }
 
-        pub fn find_overlap(&self, range: &SeedRange) -> (Option<SeedRange>, Option<SeedRange>, Option<SeedRange>) {
-            let range_end = range.0 as u128 + range.1 as u128;
+        pub fn find_overlap(&self, range: &SeedRange<T>) -> Overlap<T> {
+            let range_start = range.0.to_wide();
+            let range_end = range.end();
+            let self_start = self.source.to_wide();
             let self_end = self.end();
 
             // No overlap cases
-            if range_end <= (self.source as u128) {
+            if range_end <= self_start {
                 // Entire range is to the left of self
                 return (Some(*range), None, None);
             }
 
-            if (range.0 as u128) >= self_end {
+            if range_start >= self_end {
                 // Entire range is to the right of self
                 return (None, None, Some(*range));
             }
 
             // Overlap cases
-            let left = if range.0 < self.source {
-                Some(SeedRange(range.0, self.source - range.0))
+            let left = if range_start < self_start {
+                Some(SeedRange::from_wide(range_start, self_start - range_start))
             } else {
                 None
             };
 
-            let overlap_start = std::cmp::max(self.source, range.0) as u128;
+            let overlap_start = std::cmp::max(self_start, range_start);
             let overlap_end = std::cmp::min(self_end, range_end);
-            let overlap = Some(SeedRange(overlap_start as u32, (overlap_end - overlap_start) as u32));
+            let overlap = Some(SeedRange::from_wide(overlap_start, overlap_end - overlap_start));
 
             let right = if range_end > self_end {
-                Some(SeedRange(self_end as u32, (range_end - self_end) as u32))
+                Some(SeedRange::from_wide(self_end, range_end - self_end))
             } else {
                 None
             };
@@ -68,75 +145,114 @@ pub mod seeds {
         }
     }
 
-    impl PartialOrd for SeedMapping {
+    impl<T: SeedValue> PartialOrd for SeedMapping<T> {
         fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            self.source.partial_cmp(&other.source)
+            Some(self.cmp(other))
         }
     }
 
-    impl Ord for SeedMapping {
+    impl<T: SeedValue> Ord for SeedMapping<T> {
         fn cmp(&self, other: &Self) -> Ordering {
             self.source.cmp(&other.source)
         }
     }
 
     #[derive(Debug, Eq, PartialEq, Copy, Clone)]
-    pub struct SeedRange(u32, u32);
+    pub struct SeedRange<T: SeedValue = u64>(T, T);
+
+    impl<T: SeedValue> SeedRange<T> {
+        pub fn new(start: T, range: T) -> Self { Self(start, range) }
 
-    impl SeedRange {
-        pub fn new(start: u32, range: u32) -> Self { Self(start, range) }
+        /// Builds a range from parts of an existing range, which always fit the value type.
+        fn from_wide(start: u128, range: u128) -> Self {
+            Self(T::from_wide(start).unwrap(), T::from_wide(range).unwrap())
+        }
 
-        pub fn with_mapping(&self, mapping: &SeedMapping) -> Self {
-            Self(mapping.convert(self.0), self.1)
+        pub fn end(&self) -> u128 {
+            self.0.to_wide() + self.1.to_wide()
         }
 
-        pub fn lowest(&self) -> u32 {
+        /// Whether the last value of the range fits the value type. Only ranges that fit can be
+        /// split by mappings.
+        pub fn fits(&self) -> bool {
+            self.1.to_wide() == 0 || T::from_wide(self.end() - 1).is_some()
+        }
+
+        pub fn with_mapping(&self, mapping: &SeedMapping<T>) -> Result<Self, Overflow<T>> {
+            let start = mapping.convert(self.0)?;
+
+            // The last value of the range has to fit as well
+            if self.1.to_wide() > 0 && T::from_wide(start.to_wide() + self.1.to_wide() - 1).is_none() {
+                return Err(Overflow { value: self.0, mapping: *mapping });
+            }
+
+            Ok(Self(start, self.1))
+        }
+
+        pub fn lowest(&self) -> T {
             self.0
         }
+
+        pub fn values(&self) -> impl Iterator<Item = T> {
+            (self.0.to_wide()..self.end()).map(|value| T::from_wide(value).unwrap())
+        }
     }
 
-    impl PartialOrd for SeedRange {
+    impl<T: SeedValue> PartialOrd for SeedRange<T> {
         fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            self.0.partial_cmp(&other.0)
+            Some(self.cmp(other))
         }
     }
 
-    impl Ord for SeedRange {
+    impl<T: SeedValue> Ord for SeedRange<T> {
+        /// Orders by start and then by length, so a set keeps ranges that share a start.
         fn cmp(&self, other: &Self) -> Ordering {
-            self.0.cmp(&other.0)
+            self.0.cmp(&other.0).then(self.1.cmp(&other.1))
         }
     }
 
     #[derive(Debug)]
-    pub struct SeedMap {
+    pub struct SeedMap<T: SeedValue = u64> {
         destination: String,
-        mappings: BTreeSet<SeedMapping>,
+        /// Ordered by source. Mappings with the same source are all kept, in insertion order.
+        mappings: Vec<SeedMapping<T>>,
     }
 
-    impl SeedMap {
+    impl<T: SeedValue> SeedMap<T> {
         pub fn new(destination: String) -> Self {
             Self {
                 destination,
-                mappings: BTreeSet::new(),
+                mappings: Vec::new(),
             }
         }
 
-        pub fn add_mapping(&mut self, mapping: SeedMapping) {
-            self.mappings.insert(mapping);
+        pub fn add_mapping(&mut self, mapping: SeedMapping<T>) {
+            let index = self.mappings.partition_point(|existing| *existing <= mapping);
+            self.mappings.insert(index, mapping);
         }
 
-        pub fn convert(&self, value: u32) -> u32 {
-            for mapping in &self.mappings {
-                if mapping.in_range(value) {
-                    return mapping.convert(value);
-                }
+        pub fn destination(&self) -> &str {
+            &self.destination
+        }
+
+        pub fn mappings(&self) -> &[SeedMapping<T>] {
+            &self.mappings
+        }
+
+        pub fn find_mapping(&self, value: T) -> Option<&SeedMapping<T>> {
+            self.mappings.iter().find(|mapping| mapping.in_range(value))
+        }
+
+        pub fn convert(&self, value: T) -> Result<T, Overflow<T>> {
+            match self.find_mapping(value) {
+                Some(mapping) => mapping.convert(value),
+                None => Ok(value),
             }
-            value
         }
 
-        pub fn convert_range(&self, range: &SeedRange) -> BTreeSet<SeedRange> {
+        pub fn convert_range(&self, range: &SeedRange<T>) -> Result<BTreeSet<SeedRange<T>>, Overflow<T>> {
             let mut set = BTreeSet::new();
-            let mut current_range = range.clone();
+            let mut current_range = *range;
             let mut resting_range = true;
 
             for mapping in &self.mappings {
@@ -147,7 +263,7 @@ pub mod seeds {
                 }
 
                 if let Some(overlap) = overlap {
-                    set.insert(overlap.with_mapping(mapping));
+                    set.insert(overlap.with_mapping(mapping)?);
                 }
 
                 if let Some(right) = right {
@@ -162,58 +278,313 @@ pub mod seeds {
                 set.insert(current_range);
             }
 
-            set
+            Ok(set)
+        }
+    }
+
+    /// One conversion on the way from one category to another. A `mapping` of `None` means the
+    /// value wasn't in any mapping and was kept as is.
+    #[derive(Debug, Clone, Eq, PartialEq)]
+    pub struct TraceStep<T: SeedValue> {
+        pub source_type: String,
+        pub value_type: String,
+        pub value: T,
+        pub converted: T,
+        pub mapping: Option<SeedMapping<T>>,
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    pub enum SeedError<T: SeedValue> {
+        /// There is no map between two consecutive categories of a path.
+        NoMap(String, String),
+        Overflow(Overflow<T>),
+        /// Searching for paths between two categories took too long.
+        TooManyPaths(String, String),
+    }
+
+    impl<T: SeedValue> Display for SeedError<T> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                SeedError::NoMap(source, destination) => write!(f, "no map from {} to {}", source, destination),
+                SeedError::Overflow(overflow) => write!(f, "{}", overflow),
+                SeedError::TooManyPaths(source, destination) => write!(f, "too many paths from {} to {}", source, destination),
+            }
         }
     }
 
+    impl<T: SeedValue> From<Overflow<T>> for SeedError<T> {
+        fn from(overflow: Overflow<T>) -> Self {
+            SeedError::Overflow(overflow)
+        }
+    }
+
+    /// How many categories [`SeedMaps::paths`] visits before giving up.
+    pub const MAX_PATH_SEARCH: usize = 10_000;
+
+    /// Joins overlapping and adjacent ranges and drops empty ones, so converting ranges along a
+    /// path can't split them into ever more pieces.
+    fn merge<T: SeedValue>(ranges: BTreeSet<SeedRange<T>>) -> BTreeSet<SeedRange<T>> {
+        let mut merged: Vec<(u128, u128)> = vec![];
+
+        for range in ranges.into_iter().filter(|range| range.1.to_wide() > 0) {
+            match merged.last_mut() {
+                // A range of every value doesn't fit the value type, that one stays in two pieces
+                Some((start, end)) if range.0.to_wide() <= *end && T::from_wide(range.end().max(*end) - *start).is_some() => {
+                    *end = (*end).max(range.end());
+                }
+                _ => merged.push((range.0.to_wide(), range.end())),
+            }
+        }
+
+        merged.into_iter()
+            .map(|(start, end)| SeedRange::from_wide(start, end - start))
+            .collect()
+    }
+
+    /// Identifies a map by its source and destination category.
+    pub type MapKey = (String, String);
+
+    /// All maps of an almanac. A category can map to several other categories, so values can
+    /// reach a category along different paths.
     #[derive(Debug)]
-    pub struct SeedMaps(HashMap<String, SeedMap>);
+    pub struct SeedMaps<T: SeedValue = u64> {
+        maps: HashMap<String, Vec<SeedMap<T>>>,
+        /// Maps that were defined more than once.
+        redefined: Vec<MapKey>,
+    }
+
+    impl<T: SeedValue> Default for SeedMaps<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
 
-    impl SeedMaps {
+    impl<T: SeedValue> SeedMaps<T> {
         pub fn new() -> Self {
-            Self(HashMap::new())
+            Self {
+                maps: HashMap::new(),
+                redefined: Vec::new(),
+            }
         }
 
-        pub fn new_map(&mut self, source: &str, destination: &str) -> String {
-            let map = SeedMap::new(destination.to_string());
-            let key = source.to_string();
+        /// Adds an empty map from `source` to `destination`. A map that was already defined
+        /// between them is replaced, which is recorded as a redefinition.
+        pub fn new_map(&mut self, source: &str, destination: &str) -> MapKey {
+            let key = (source.to_string(), destination.to_string());
+            let maps = self.maps.entry(source.to_string()).or_default();
 
-            self.0.insert(source.to_string(), map);
+            if let Some(index) = maps.iter().position(|map| map.destination == destination) {
+                maps.remove(index);
+                self.redefined.push(key.clone());
+            }
+
+            maps.push(SeedMap::new(destination.to_string()));
 
             key
         }
 
-        pub fn get_mut_map(&mut self, source: &str) -> &mut SeedMap {
-            self.0.get_mut(source).unwrap()
+        pub fn get_mut_map(&mut self, (source, destination): &MapKey) -> &mut SeedMap<T> {
+            self.maps.get_mut(source)
+                .and_then(|maps| maps.iter_mut().find(|map| map.destination == *destination))
+                .unwrap()
         }
 
-        pub fn get_value(&self, value: u32, source_type: &str, value_type: &str) -> Option<u32> {
-            if source_type == value_type {
-                return Some(value);
+        pub fn get_map(&self, source: &str, destination: &str) -> Option<&SeedMap<T>> {
+            self.maps_from(source)
+                .iter()
+                .find(|map| map.destination == destination)
+        }
+
+        /// Every map from `source`, in the order they were defined.
+        pub fn maps_from(&self, source: &str) -> &[SeedMap<T>] {
+            self.maps.get(source).map_or(&[], |maps| maps.as_slice())
+        }
+
+        /// Every category that has at least one map from it.
+        pub fn sources(&self) -> impl Iterator<Item = &str> {
+            self.maps.keys().map(|key| key.as_str())
+        }
+
+        pub fn redefined(&self) -> &[MapKey] {
+            &self.redefined
+        }
+
+        /// Every path of categories from `source_type` to `value_type` that doesn't visit a
+        /// category twice, including both ends. The number of paths grows exponentially with the
+        /// number of maps, so the search gives up after visiting [`MAX_PATH_SEARCH`] categories.
+        pub fn paths(&self, source_type: &str, value_type: &str) -> Result<Vec<Vec<String>>, SeedError<T>> {
+            let mut paths = vec![];
+            let mut path = vec![source_type.to_string()];
+            let mut budget = MAX_PATH_SEARCH;
+
+            self.find_paths(&mut path, value_type, &mut paths, &mut budget)?;
+
+            Ok(paths)
+        }
+
+        fn find_paths(&self, path: &mut Vec<String>, value_type: &str, paths: &mut Vec<Vec<String>>, budget: &mut usize) -> Result<(), SeedError<T>> {
+            let category = path.last().unwrap().clone();
+
+            *budget = budget.checked_sub(1)
+                .ok_or_else(|| SeedError::TooManyPaths(path[0].clone(), value_type.to_string()))?;
+
+            if category == value_type {
+                paths.push(path.clone());
+                return Ok(());
             }
 
-            if let Some(map) = self.0.get(source_type) {
-                let next_value = map.convert(value);
+            for map in self.maps_from(&category) {
+                if !path.contains(&map.destination) {
+                    path.push(map.destination.clone());
+                    self.find_paths(path, value_type, paths, budget)?;
+                    path.pop();
+                }
+            }
 
-                self.get_value(next_value, map.destination.as_str(), value_type)
-            } else {
-                None
+            Ok(())
+        }
+
+        fn maps_along<'a>(&'a self, path: &'a [String]) -> impl Iterator<Item = Result<&'a SeedMap<T>, SeedError<T>>> + 'a {
+            path.windows(2).map(|step| self.get_map(&step[0], &step[1])
+                .ok_or_else(|| SeedError::NoMap(step[0].clone(), step[1].clone())))
+        }
+
+        /// Converts a value along a path from `paths`.
+        pub fn get_value(&self, value: T, path: &[String]) -> Result<T, SeedError<T>> {
+            let mut value = value;
+
+            for map in self.maps_along(path) {
+                value = map?.convert(value)?;
             }
+
+            Ok(value)
         }
 
-        pub fn get_value_for_ranges(&self, pairs: BTreeSet<SeedRange>, source_type: &str, value_type: &str) -> Option<BTreeSet<SeedRange>> {
-            if source_type == value_type {
-                return Some(pairs);
+        /// Like `get_value`, but returns every conversion made along the way.
+        pub fn trace(&self, value: T, path: &[String]) -> Result<Vec<TraceStep<T>>, SeedError<T>> {
+            let mut steps = vec![];
+            let mut value = value;
+
+            for (step, map) in path.windows(2).zip(self.maps_along(path)) {
+                let map = map?;
+                let converted = map.convert(value)?;
+
+                steps.push(TraceStep {
+                    source_type: step[0].clone(),
+                    value_type: step[1].clone(),
+                    value,
+                    converted,
+                    mapping: map.find_mapping(value).copied(),
+                });
+
+                value = converted;
             }
 
-            if let Some(map) = self.0.get(source_type) {
-                let new_pairs: BTreeSet<SeedRange> = pairs.iter()
-                    .flat_map(|range| map.convert_range(range))
-                    .collect();
+            Ok(steps)
+        }
 
-                self.get_value_for_ranges(new_pairs, map.destination.as_str(), value_type)
-            } else {
-                None
+        /// Converts sets of ranges along a path from `paths`.
+        pub fn get_value_for_ranges(&self, pairs: BTreeSet<SeedRange<T>>, path: &[String]) -> Result<BTreeSet<SeedRange<T>>, SeedError<T>> {
+            let mut pairs = pairs;
+
+            for map in self.maps_along(path) {
+                let map = map?;
+                let mut new_pairs = BTreeSet::new();
+
+                for range in &pairs {
+                    new_pairs.extend(map.convert_range(range)?);
+                }
+
+                pairs = merge(new_pairs);
+            }
+
+            Ok(pairs)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use proptest::prelude::*;
+
+        use super::*;
+
+        /// A range of `u8` values that fits the type, so it can reach the end of it.
+        fn seed_range() -> impl Strategy<Value = SeedRange<u8>> {
+            any::<u8>()
+                .prop_flat_map(|start| (Just(start), 0..=(256 - start as u16).min(255) as u8))
+                .prop_map(|(start, range)| SeedRange::new(start, range))
+        }
+
+        fn seed_mapping() -> impl Strategy<Value = SeedMapping<u8>> {
+            (seed_range(), any::<u8>())
+                .prop_map(|(source, destination)| SeedMapping { source: source.0, destination, range: source.1 })
+        }
+
+        /// A map that shuffles blocks of consecutive values around, so like the maps of a puzzle
+        /// it converts every value to a different one.
+        fn shuffling_map() -> impl Strategy<Value = SeedMap<u8>> {
+            prop::collection::vec(1..=31_u8, 1..=8)
+                .prop_flat_map(|sizes| {
+                    let order = Just((0..sizes.len()).collect::<Vec<_>>()).prop_shuffle();
+                    (Just(sizes), order)
+                })
+                .prop_map(|(sizes, order)| {
+                    let sources: Vec<u8> = sizes.iter()
+                        .scan(0, |source, size| {
+                            *source += size;
+                            Some(*source - size)
+                        })
+                        .collect();
+
+                    let mut map = SeedMap::new("location".to_string());
+                    let mut destination = 0;
+
+                    for block in order {
+                        map.add_mapping(SeedMapping { source: sources[block], destination, range: sizes[block] });
+                        destination += sizes[block];
+                    }
+
+                    map
+                })
+        }
+
+        #[test]
+        fn ranges_fit_up_to_the_last_value() {
+            assert!(SeedRange::new(200_u8, 56).fits());
+            assert!(SeedRange::new(255_u8, 0).fits());
+            assert!(!SeedRange::new(200_u8, 57).fits());
+            assert_eq!(SeedMapping::<u8>::parse("0 200 57").unwrap_err(), "source range 200 57 is too large");
+        }
+
+        proptest! {
+            #[test]
+            fn overlap_pieces_partition_the_range(mapping in seed_mapping(), range in seed_range()) {
+                let (left, overlap, right) = mapping.find_overlap(&range);
+                let mut next_start = range.0.to_wide();
+
+                for piece in [left, overlap, right].into_iter().flatten() {
+                    prop_assert_eq!(piece.0.to_wide(), next_start);
+                    next_start = piece.end();
+                }
+
+                prop_assert_eq!(next_start, range.end());
+
+                if let Some(left) = left {
+                    prop_assert!(left.end() <= mapping.source.to_wide());
+                }
+                if let Some(overlap) = overlap {
+                    prop_assert!(overlap.0 >= mapping.source && overlap.end() <= mapping.end());
+                }
+                if let Some(right) = right {
+                    prop_assert!(right.0.to_wide() >= mapping.end());
+                }
+            }
+
+            #[test]
+            fn converting_a_range_keeps_its_length(map in shuffling_map(), range in seed_range()) {
+                let converted = map.convert_range(&range).unwrap();
+
+                prop_assert_eq!(converted.iter().map(|range| range.1.to_wide()).sum::<u128>(), range.1.to_wide());
             }
         }
     }
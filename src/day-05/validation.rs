@@ -0,0 +1,316 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+
+use super::seeds::seeds::{SeedMap, SeedMapping, SeedMaps, SeedValue};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum AlmanacIssue<T: SeedValue> {
+    /// Two mappings in one map share source values, so the result depends on their order.
+    Overlap { category: String, first: SeedMapping<T>, second: SeedMapping<T> },
+    /// Two mappings in one map have exactly the same source range.
+    Duplicate { category: String, first: SeedMapping<T>, second: SeedMapping<T> },
+    /// Source values between two mappings of a map that fall back to the identity mapping.
+    Gap { category: String, start: T, end: T },
+    /// No chain of maps leads from one category to the other. Every path stops at one of the
+    /// dead ends, which have no maps from them, or loops back when there are none.
+    NoPath { from: String, to: String, dead_ends: Vec<String> },
+    /// Following the maps from a category leads back to it.
+    Cycle(Vec<String>),
+    /// A map was defined more than once and only the last definition is used.
+    Redefined(String),
+}
+
+impl<T: SeedValue> AlmanacIssue<T> {
+    pub fn severity(&self) -> Severity {
+        match self {
+            AlmanacIssue::Gap { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl<T: SeedValue> Display for AlmanacIssue<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mapping = |mapping: &SeedMapping<T>| format!(
+            "{} {} {}",
+            mapping.destination(),
+            mapping.source(),
+            mapping.range(),
+        );
+
+        match self {
+            AlmanacIssue::Overlap { category, first, second } =>
+                write!(f, "{} map: mappings '{}' and '{}' overlap", category, mapping(first), mapping(second)),
+            AlmanacIssue::Duplicate { category, first, second } =>
+                write!(f, "{} map: mappings '{}' and '{}' have the same source range", category, mapping(first), mapping(second)),
+            AlmanacIssue::Gap { category, start, end } =>
+                write!(f, "{} map: values {}..{} are not mapped", category, start, end),
+            AlmanacIssue::NoPath { from, to, dead_ends } if dead_ends.is_empty() =>
+                write!(f, "no path from {} to {}: every path from {} loops back", from, to, from),
+            AlmanacIssue::NoPath { from, to, dead_ends } =>
+                write!(f, "no path from {} to {}: there are no maps from {}", from, to, dead_ends.join(", ")),
+            AlmanacIssue::Cycle(categories) =>
+                write!(f, "maps form a cycle: {} -> {}", categories.join(" -> "), categories[0]),
+            AlmanacIssue::Redefined(category) =>
+                write!(f, "{} map is defined more than once", category),
+        }
+    }
+}
+
+/// Checks every map, whether `to` can be reached from `from` and the maps as a whole.
+pub fn validate<T: SeedValue>(seed_maps: &SeedMaps<T>, from: &str, to: &str) -> Vec<AlmanacIssue<T>> {
+    let mut issues: Vec<AlmanacIssue<T>> = seed_maps.redefined()
+        .iter()
+        .map(|(source, destination)| AlmanacIssue::Redefined(format!("{}-to-{}", source, destination)))
+        .collect();
+
+    let mut sources: Vec<&str> = seed_maps.sources().collect();
+    sources.sort();
+
+    for source in &sources {
+        for map in seed_maps.maps_from(source) {
+            issues.extend(validate_map(&format!("{}-to-{}", source, map.destination()), map));
+        }
+    }
+
+    issues.extend(find_cycles(seed_maps, &sources));
+
+    // Too many paths to search through still means there is one
+    if seed_maps.paths(from, to).is_ok_and(|paths| paths.is_empty()) {
+        issues.push(AlmanacIssue::NoPath {
+            from: from.to_string(),
+            to: to.to_string(),
+            dead_ends: find_dead_ends(seed_maps, from),
+        });
+    }
+
+    issues
+}
+
+/// Validates the almanac from seed to location, failing with its errors. Warnings are only
+/// printed when `verbose`.
+pub fn check_almanac<T: SeedValue>(seed_maps: &SeedMaps<T>, verbose: bool) -> Result<(), String> {
+    let (errors, warnings): (Vec<_>, Vec<_>) = validate(seed_maps, "seed", "location")
+        .into_iter()
+        .partition(|issue| issue.severity() == Severity::Error);
+
+    if verbose {
+        for warning in &warnings {
+            eprintln!("warning: {}", warning);
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(format!("invalid almanac: {}", errors.iter().join("; ")));
+    }
+
+    Ok(())
+}
+
+/// Categories reachable from `from` that have no maps from them.
+fn find_dead_ends<T: SeedValue>(seed_maps: &SeedMaps<T>, from: &str) -> Vec<String> {
+    let mut visited: BTreeSet<&str> = BTreeSet::new();
+    let mut queue = vec![from];
+    let mut dead_ends = vec![];
+
+    while let Some(category) = queue.pop() {
+        if !visited.insert(category) {
+            continue;
+        }
+
+        let maps = seed_maps.maps_from(category);
+
+        if maps.is_empty() {
+            dead_ends.push(category.to_string());
+        }
+
+        queue.extend(maps.iter().map(|map| map.destination()));
+    }
+
+    dead_ends.sort();
+    dead_ends
+}
+
+fn validate_map<T: SeedValue>(category: &str, map: &SeedMap<T>) -> Vec<AlmanacIssue<T>> {
+    let mut issues = vec![];
+    let mut furthest: Option<&SeedMapping<T>> = None;
+
+    // Mappings are ordered by source, so each one only has to be compared with the mapping
+    // reaching furthest before it.
+    for mapping in map.mappings() {
+        if let Some(previous) = furthest {
+            let category = category.to_string();
+
+            if previous.source() == mapping.source() && previous.range() == mapping.range() {
+                issues.push(AlmanacIssue::Duplicate { category, first: *previous, second: *mapping });
+            } else if mapping.source().to_wide() < previous.end() {
+                issues.push(AlmanacIssue::Overlap { category, first: *previous, second: *mapping });
+            } else if mapping.source().to_wide() > previous.end() {
+                issues.push(AlmanacIssue::Gap {
+                    category,
+                    start: T::from_wide(previous.end()).unwrap(),
+                    end: mapping.source(),
+                });
+            }
+        }
+
+        if furthest.is_none_or(|previous| mapping.end() > previous.end()) {
+            furthest = Some(mapping);
+        }
+    }
+
+    issues
+}
+
+/// Finds cycles with a single depth-first search that colours categories as it goes, so every map
+/// is followed once however dense the almanac. Every map leading back to a category on the search
+/// path closes a cycle, which is reported; cycles sharing those maps are not enumerated.
+fn find_cycles<T: SeedValue>(seed_maps: &SeedMaps<T>, sources: &[&str]) -> Vec<AlmanacIssue<T>> {
+    let mut cycles: BTreeSet<Vec<String>> = BTreeSet::new();
+    let mut finished: HashSet<&str> = HashSet::new();
+
+    for source in sources {
+        if finished.contains(source) {
+            continue;
+        }
+
+        // The search path, with the index of the next map to follow from each category on it
+        let mut path: Vec<(&str, usize)> = vec![(source, 0)];
+        let mut on_path: HashMap<&str, usize> = HashMap::from([(*source, 0)]);
+
+        while let Some(&(category, next_map)) = path.last() {
+            let Some(map) = seed_maps.maps_from(category).get(next_map) else {
+                finished.insert(category);
+                on_path.remove(category);
+                path.pop();
+                continue;
+            };
+
+            path.last_mut().unwrap().1 += 1;
+            let next = map.destination();
+
+            if let Some(&index) = on_path.get(next) {
+                // Rotate the cycle to start at its smallest category, so it's only reported once
+                let mut cycle: Vec<String> = path[index..].iter().map(|(category, _)| category.to_string()).collect();
+                let smallest = cycle.iter().enumerate().min_by_key(|(_, category)| *category).unwrap().0;
+                cycle.rotate_left(smallest);
+                cycles.insert(cycle);
+            } else if !finished.contains(next) {
+                on_path.insert(next, path.len());
+                path.push((next, 0));
+            }
+        }
+    }
+
+    cycles.into_iter().map(AlmanacIssue::Cycle).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An almanac of the maps between the categories, each with its mappings.
+    fn almanac(maps: &[(&str, &str, &[&str])]) -> SeedMaps<u64> {
+        let mut seed_maps = SeedMaps::new();
+
+        for (source, destination, mappings) in maps {
+            let key = seed_maps.new_map(source, destination);
+
+            for mapping in *mappings {
+                seed_maps.get_mut_map(&key).add_mapping(SeedMapping::parse(mapping).unwrap());
+            }
+        }
+
+        seed_maps
+    }
+
+    fn cycles(seed_maps: &SeedMaps<u64>) -> Vec<AlmanacIssue<u64>> {
+        let mut sources: Vec<&str> = seed_maps.sources().collect();
+        sources.sort();
+
+        find_cycles(seed_maps, &sources)
+    }
+
+    #[test]
+    fn finds_overlaps_duplicates_and_gaps() {
+        let seed_maps = almanac(&[("seed", "location", &["50 0 10", "60 5 10", "70 20 5", "80 20 5"])]);
+        let mapping = |mapping: &str| SeedMapping::parse(mapping).unwrap();
+        let category = "seed-to-location".to_string();
+
+        assert_eq!(validate(&seed_maps, "seed", "location"), vec![
+            AlmanacIssue::Overlap { category: category.clone(), first: mapping("50 0 10"), second: mapping("60 5 10") },
+            AlmanacIssue::Gap { category: category.clone(), start: 15, end: 20 },
+            AlmanacIssue::Duplicate { category, first: mapping("70 20 5"), second: mapping("80 20 5") },
+        ]);
+    }
+
+    #[test]
+    fn finds_redefined_maps() {
+        let seed_maps = almanac(&[("seed", "location", &["0 0 1"]), ("seed", "location", &["5 0 1"])]);
+
+        assert_eq!(validate(&seed_maps, "seed", "location"), vec![AlmanacIssue::Redefined("seed-to-location".to_string())]);
+    }
+
+    #[test]
+    fn paths_that_only_loop_back_have_no_dead_ends() {
+        let no_path = |seed_maps: &SeedMaps<u64>| validate(seed_maps, "seed", "location").pop().unwrap().to_string();
+
+        assert_eq!(
+            no_path(&almanac(&[("seed", "soil", &[]), ("soil", "seed", &[])])),
+            "no path from seed to location: every path from seed loops back",
+        );
+        assert_eq!(
+            no_path(&almanac(&[("seed", "soil", &[])])),
+            "no path from seed to location: there are no maps from soil",
+        );
+    }
+
+    #[test]
+    fn only_errors_fail_the_check() {
+        let gap = almanac(&[("seed", "location", &["50 0 10", "70 20 5"])]);
+        let overlap = almanac(&[("seed", "location", &["50 0 10", "60 5 10"])]);
+
+        assert_eq!(check_almanac(&gap, false), Ok(()));
+        assert_eq!(
+            check_almanac(&overlap, false),
+            Err("invalid almanac: seed-to-location map: mappings '50 0 10' and '60 5 10' overlap".to_string()),
+        );
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let mut seed_maps = SeedMaps::new();
+        for (source, destination) in [("soil", "water"), ("seed", "soil"), ("water", "seed"), ("water", "light")] {
+            seed_maps.new_map(source, destination);
+        }
+
+        let cycle = ["seed", "soil", "water"].map(String::from).to_vec();
+        assert_eq!(cycles(&seed_maps), vec![AlmanacIssue::Cycle(cycle)]);
+    }
+
+    #[test]
+    fn dense_almanacs_are_searched_once() {
+        // Every category maps to every later one, so there are 2^38 paths from the first to the
+        // last, and the last maps back to the first
+        let categories: Vec<String> = (0..40).map(|index| format!("c{:02}", index)).collect();
+        let mut seed_maps = SeedMaps::new();
+
+        for (index, source) in categories.iter().enumerate() {
+            for destination in &categories[index + 1..] {
+                seed_maps.new_map(source, destination);
+            }
+        }
+        seed_maps.new_map(&categories[39], &categories[0]);
+
+        assert_eq!(cycles(&seed_maps), vec![AlmanacIssue::Cycle(categories.clone())]);
+        assert!(validate(&seed_maps, "c00", "c39").contains(&AlmanacIssue::Cycle(categories)));
+    }
+}
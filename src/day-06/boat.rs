@@ -0,0 +1,117 @@
+/// How a boat picks up speed while its button is held.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Boat {
+    /// Speed gained in mm/ms for every millisecond the button is held.
+    pub acceleration: u64,
+    /// Speed the boat can't go faster than, if any.
+    pub max_speed: Option<u64>,
+    /// Milliseconds the button has to be held before the boat starts gaining speed.
+    pub charge_delay: u64,
+}
+
+impl Default for Boat {
+    fn default() -> Self {
+        Self { acceleration: 1, max_speed: None, charge_delay: 0 }
+    }
+}
+
+impl Boat {
+    pub fn speed(&self, hold: u64) -> u64 {
+        let speed = hold.saturating_sub(self.charge_delay).saturating_mul(self.acceleration);
+
+        self.max_speed.map_or(speed, |max_speed| speed.min(max_speed))
+    }
+
+    pub fn distance(&self, max_time: u64, hold: u64) -> u128 {
+        self.speed(hold) as u128 * max_time.saturating_sub(hold) as u128
+    }
+
+    /// The first hold time at which the speed is capped, if the cap is reached at all.
+    fn capped_from(&self, max_time: u64) -> Option<u64> {
+        let max_speed = self.max_speed?;
+
+        if self.acceleration == 0 {
+            return if max_speed == 0 { Some(0) } else { None };
+        }
+
+        Some(self.charge_delay + max_speed.div_ceil(self.acceleration))
+            .filter(|hold| *hold <= max_time)
+    }
+
+    /// The hold time that goes the furthest.
+    pub fn optimal_hold(&self, max_time: u64) -> u64 {
+        // Uncapped the distance is a parabola peaking halfway between the delay and the end
+        let peak = (self.charge_delay.min(max_time) + max_time) / 2;
+        let mut candidates = vec![peak, (peak + 1).min(max_time)];
+
+        // Capped the distance only falls, so the best hold is either side of reaching the cap
+        if let Some(capped_from) = self.capped_from(max_time) {
+            candidates.extend([capped_from.saturating_sub(1), capped_from]);
+        }
+
+        candidates.into_iter()
+            .max_by_key(|hold| (self.distance(max_time, *hold), u64::MAX - hold))
+            .unwrap_or(0)
+    }
+
+    /// The first and last hold time that go further than `record`, if any. The distance only
+    /// increases up to the optimal hold time and only decreases after it, so these bound every
+    /// winning hold time.
+    pub fn winning_holds(&self, max_time: u64, record: u64) -> Option<(u64, u64)> {
+        let wins = |hold: u64| self.distance(max_time, hold) > record as u128;
+        let optimal_hold = self.optimal_hold(max_time);
+
+        if !wins(optimal_hold) {
+            return None;
+        }
+
+        let (first, last) = self.quadratic_roots(max_time, record);
+        let last = match self.capped_from(max_time) {
+            // Capped the distance falls linearly: max_speed * (max_time - hold) > record
+            Some(capped_from) if last >= capped_from as f64 => {
+                let max_speed = self.max_speed.unwrap();
+                (max_time - record / max_speed).saturating_sub(1) as f64
+            }
+            _ => last,
+        };
+
+        Some((
+            correct(first, 0, optimal_hold, &wins, true),
+            correct(last, optimal_hold, max_time, &wins, false),
+        ))
+    }
+
+    /// Approximate roots of acceleration * (hold - charge_delay) * (max_time - hold) = record.
+    fn quadratic_roots(&self, max_time: u64, record: u64) -> (f64, f64) {
+        let a: f64 = -(self.acceleration as f64);
+        let b: f64 = self.acceleration as f64 * (self.charge_delay + max_time) as f64;
+        let c: f64 = -(self.acceleration as f64 * self.charge_delay as f64 * max_time as f64) - record as f64;
+        let d: f64 = (b.powf(2f64) - (4f64 * a * c)).max(0f64);
+
+        ((-b + d.sqrt()) / (2f64 * a), (-b - d.sqrt()) / (2f64 * a))
+    }
+}
+
+/// Moves an estimated boundary of the winning hold times, within `from..=to`, onto the exact
+/// first (or last) hold time that wins.
+fn correct(estimate: f64, from: u64, to: u64, wins: &impl Fn(u64) -> bool, first: bool) -> u64 {
+    let mut hold = (estimate.max(from as f64) as u64).clamp(from, to);
+
+    if first {
+        while hold > from && wins(hold - 1) {
+            hold -= 1;
+        }
+        while !wins(hold) {
+            hold += 1;
+        }
+    } else {
+        while hold < to && wins(hold + 1) {
+            hold += 1;
+        }
+        while !wins(hold) {
+            hold -= 1;
+        }
+    }
+
+    hold
+}
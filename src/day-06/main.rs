@@ -1,75 +1,99 @@
-use std::io;
-
-fn parse_input_strings() -> (String, String) {
-    let mut time_str = String::new();
-    let mut distance_str = String::new();
-
-    let io_result = (
-        io::stdin().read_line(&mut time_str).unwrap(),
-        io::stdin().read_line(&mut distance_str).unwrap()
-    );
-
-    if io_result.0 == 0 || io_result.1 == 0 {
-        panic!("Could not read input");
+use advent_of_code_2023::answers::Report;
+use advent_of_code_2023::day06::boat::Boat;
+use advent_of_code_2023::day06::{analyse_race, parse_input, solve_with, RaceAnalysis};
+use advent_of_code_2023::input;
+use advent_of_code_2023::input::InputError;
+use advent_of_code_2023::output::Output;
+use advent_of_code_2023::timings::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn parse_boat(args: Vec<String>) -> Result<Boat, InputError> {
+    let mut boat = Boat::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let value = args.next()
+            .ok_or_else(|| InputError::InvalidArgument(format!("missing value for {}", arg)))?;
+        let value: u64 = input::parse_value(&arg, &value)?;
+
+        match arg.as_str() {
+            "--acceleration" => boat.acceleration = value,
+            "--max-speed" => boat.max_speed = Some(value),
+            "--charge-delay" => boat.charge_delay = value,
+            _ => return Err(InputError::InvalidArgument(format!("unknown argument {}", arg))),
+        }
     }
 
-    (
-        time_str.strip_prefix("Time:").unwrap().trim().to_string(),
-        distance_str.strip_prefix("Distance:").unwrap().trim().to_string()
-    )
+    Ok(boat)
 }
 
-fn parse_races((time_str, distance_str): (&str, &str)) -> Vec<(u64, u64)> {
-    time_str
-        .split_whitespace()
-        .map(|time| time.parse().unwrap())
-        .zip(distance_str.split_whitespace()
-            .map(|time| time.parse().unwrap())
-        )
+fn print_races(boat: &Boat, races: &[(u64, u64)]) -> Vec<RaceAnalysis> {
+    println!("race\ttime\trecord\tfirst\tlast\twins\tbest hold\tbest distance\tmargin");
+
+    races.iter()
+        .enumerate()
+        .map(|(index, race)| {
+            let analysis = analyse_race(boat, race);
+            let (first, last) = analysis.winning_holds
+                .map_or(("-".to_string(), "-".to_string()), |(first, last)| (first.to_string(), last.to_string()));
+
+            println!(
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t\t{}\t\t{}",
+                index + 1,
+                race.0,
+                race.1,
+                first,
+                last,
+                analysis.wins(),
+                analysis.optimal_hold,
+                analysis.max_distance,
+                analysis.margin(race.1).map_or("-".to_string(), |margin| margin.to_string()),
+            );
+
+            analysis
+        })
         .collect()
 }
 
-fn parse_race((time_str, distance_str): (&str, &str)) -> (u64, u64) {
-    (
-        time_str.replace(" ", "").parse().unwrap(),
-        distance_str.replace(" ", "").parse().unwrap(),
-    )
-}
-
-fn number_of_possible_wins((max_time, required_distance): &(u64, u64)) -> u32 {
-    println!("Race\tmax_time={}\trequired_distance={}", max_time, required_distance);
-
-    let a: f64 = -1f64;
-    let b: f64 = *max_time as f64;
-    let c: f64 = *required_distance as f64 * -1f64;
-
-    let d: f64 = b.powf(2f64) - (4f64 * a * c);
-
-    if d == 0f64 {
-        1
-    } else if d < 0f64 {
-        0
-    } else {
-        let x1 = ((b * -1f64 + d.sqrt()) / 2f64 * a + 0.00001).ceil() as u32;
-        let x2 = ((b * -1f64 - d.sqrt()) / 2f64 * a - 0.00001).floor() as u32;
-
-        x2 - x1 + 1
-    }
-}
-
 fn main() {
-    let input = parse_input_strings();
-    let input_ptr = (input.0.as_str(), input.1.as_str());
+    let (mut input, mut args) = input::open(6);
+    let mut report = Report::new(6, &input.source, &mut args);
+
+    let output = input::exit_on_error(Output::from_args(&mut args));
+    let boat = input::exit_on_error(parse_boat(args));
+
+    if output.results_only() {
+        // Only the puzzle's boat has known answers
+        if boat != Boat::default() {
+            report.leave_unchecked(1);
+            report.leave_unchecked(2);
+        }
+
+        let solution = input::exit_on_error(solve_with(&mut input, &boat));
+        output.print(&report.results(&solution), &[solution]);
+        report.finish();
+        return;
+    }
 
-    let races = parse_races(input_ptr);
-    let big_race = parse_race(input_ptr);
+    let races = input::exit_on_error(parse_input(&mut input));
 
-    let races_result: u128 = races.iter()
-        .map(|race| number_of_possible_wins(race) as u128)
+    let races_result: u128 = print_races(&boat, &races.small).iter()
+        .map(|analysis| analysis.wins() as u128)
         .product();
 
-    let race_result = number_of_possible_wins(&big_race);
+    println!();
+    let race_result = print_races(&boat, &[races.big])[0].wins();
 
-    println!("small races = {}", races_result);
-    println!("big race    = {}", race_result);
+    println!();
+    // Only the puzzle's boat has known answers
+    if boat == Boat::default() {
+        report.answer(1, "small races", races_result);
+        report.answer(2, "big race   ", race_result);
+        report.finish();
+    } else {
+        println!("small races = {}", races_result);
+        println!("big race    = {}", race_result);
+    }
 }
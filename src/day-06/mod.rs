@@ -0,0 +1,159 @@
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::input::read_error;
+use crate::solution::Solution;
+
+use boat::Boat;
+
+pub mod boat;
+
+/// The small races from the input, and the one big race read by ignoring the spaces.
+#[derive(Debug)]
+pub struct Races {
+    pub small: Vec<(u64, u64)>,
+    pub big: (u64, u64),
+}
+
+fn parse_input_strings(mut input: impl BufRead) -> Result<(String, String), String> {
+    let mut time_str = String::new();
+    let mut distance_str = String::new();
+
+    let io_result = (
+        input.read_line(&mut time_str).map_err(read_error)?,
+        input.read_line(&mut distance_str).map_err(read_error)?
+    );
+
+    if io_result.0 == 0 || io_result.1 == 0 {
+        return Err("expected a time and a distance line".to_string());
+    }
+
+    Ok((
+        time_str.strip_prefix("Time:").ok_or("no 'Time:' on the first line")?.trim().to_string(),
+        distance_str.strip_prefix("Distance:").ok_or("no 'Distance:' on the second line")?.trim().to_string()
+    ))
+}
+
+fn parse_numbers(numbers: &str) -> Result<Vec<u64>, String> {
+    numbers.split_whitespace()
+        .map(|number| number.parse().map_err(|err| format!("invalid number {}: {}", number, err)))
+        .collect()
+}
+
+fn parse_races((time_str, distance_str): (&str, &str)) -> Result<Vec<(u64, u64)>, String> {
+    let times = parse_numbers(time_str)?;
+    let distances = parse_numbers(distance_str)?;
+
+    if times.len() != distances.len() {
+        return Err(format!("{} times but {} distances", times.len(), distances.len()));
+    }
+
+    Ok(times.into_iter().zip(distances).collect())
+}
+
+fn parse_race((time_str, distance_str): (&str, &str)) -> Result<(u64, u64), String> {
+    let parse = |str: &str| str.replace(" ", "").parse()
+        .map_err(|err| format!("invalid big race {}: {}", str, err));
+
+    Ok((parse(time_str)?, parse(distance_str)?))
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RaceAnalysis {
+    /// First and last hold time that beat the record, if any.
+    pub winning_holds: Option<(u64, u64)>,
+    /// Hold time reaching the furthest, and how far it goes.
+    pub optimal_hold: u64,
+    pub max_distance: u128,
+}
+
+impl RaceAnalysis {
+    pub fn wins(&self) -> u64 {
+        self.winning_holds.map_or(0, |(first, last)| last - first + 1)
+    }
+
+    /// How far the optimal hold time beats the record by.
+    pub fn margin(&self, record: u64) -> Option<u128> {
+        self.max_distance.checked_sub(record as u128).filter(|margin| *margin > 0)
+    }
+}
+
+pub fn analyse_race(boat: &Boat, (max_time, required_distance): &(u64, u64)) -> RaceAnalysis {
+    let optimal_hold = boat.optimal_hold(*max_time);
+
+    RaceAnalysis {
+        winning_holds: boat.winning_holds(*max_time, *required_distance),
+        optimal_hold,
+        max_distance: boat.distance(*max_time, optimal_hold),
+    }
+}
+
+pub fn parse_input(input: impl BufRead) -> Result<Races, String> {
+    let input = parse_input_strings(input)?;
+    let input_ptr = (input.0.as_str(), input.1.as_str());
+
+    Ok(Races {
+        small: parse_races(input_ptr)?,
+        big: parse_race(input_ptr)?,
+    })
+}
+
+/// Product of the number of ways to win each small race with the boat.
+pub fn small_races(races: &Races, boat: &Boat) -> Result<Answer, String> {
+    races.small.iter()
+        .map(|race| analyse_race(boat, race).wins() as u128)
+        .try_fold(1_u128, |product, wins| product.checked_mul(wins))
+        .map(Answer::from)
+        .ok_or_else(|| "product of the ways to win doesn't fit in 128 bits".to_string())
+}
+
+/// Number of ways to win the big race with the boat.
+pub fn big_race(races: &Races, boat: &Boat) -> Result<Answer, String> {
+    Ok(analyse_race(boat, &races.big).wins().into())
+}
+
+/// Product of the number of ways to win each small race.
+pub fn part1(races: &Races) -> Result<Answer, String> {
+    small_races(races, &Boat::default())
+}
+
+/// Number of ways to win the big race.
+pub fn part2(races: &Races) -> Result<Answer, String> {
+    big_race(races, &Boat::default())
+}
+
+pub fn solve(input: impl BufRead) -> Result<Solution, String> {
+    solve_with(input, &Boat::default())
+}
+
+/// Solves both parts with the boat instead of the puzzle's.
+pub fn solve_with(input: impl BufRead, boat: &Boat) -> Result<Solution, String> {
+    let mut solution = Solution::new(6);
+    let races = solution.parse(|| parse_input(input))?;
+    solution.solve_both(|| small_races(&races, boat), || big_race(&races, boat))?;
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn boat() -> impl Strategy<Value = Boat> {
+        (0..=5_u64, prop::option::of(0..=40_u64), 0..=10_u64)
+            .prop_map(|(acceleration, max_speed, charge_delay)| Boat { acceleration, max_speed, charge_delay })
+    }
+
+    proptest! {
+        #[test]
+        fn counts_match_brute_force(boat in boat(), max_time in 0..=200_u64, record in 0..=5_000_u64) {
+            let analysis = analyse_race(&boat, &(max_time, record));
+            let wins = (0..=max_time).filter(|hold| boat.distance(max_time, *hold) > record as u128).count();
+            let max_distance = (0..=max_time).map(|hold| boat.distance(max_time, hold)).max().unwrap();
+
+            prop_assert_eq!(analysis.wins(), wins as u64);
+            prop_assert_eq!(analysis.max_distance, max_distance);
+        }
+    }
+}
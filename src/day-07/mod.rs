@@ -0,0 +1,249 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::io::BufRead;
+
+use itertools::Itertools;
+
+use crate::answer::Answer;
+use crate::input::read_error;
+use crate::solution::Solution;
+
+use HandError::{InvalidCard, WrongLength};
+
+/// How many times each item occurs, most frequent first. Items that occur as often are ordered
+/// from high to low, so equal items always give the same histogram.
+fn make_histogram<T>(items: Vec<T>) -> Vec<(T, usize)>
+    where T: Ord + Clone + Hash {
+    let mut histogram: HashMap<T, usize> = HashMap::new();
+
+    for item in items {
+        *histogram.entry(item.clone()).or_insert(0) += 1;
+    }
+
+    histogram.into_iter()
+        .sorted_by(|(a_item, a), (b_item, b)| b.cmp(a).then_with(|| b_item.cmp(a_item)))
+        .collect()
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Card(char);
+
+impl Card {
+    pub fn new(card_type: char) -> Self { Self(card_type) }
+
+    pub fn strength(&self) -> u32 {
+        match self.0 {
+            digit @ '2'..='9' => digit.to_digit(10).unwrap(),
+            'J' => 1,
+            'T' => 11,
+            'Q' => 12,
+            'K' => 13,
+            'A' => 14,
+            _ => 0
+        }
+    }
+}
+
+impl PartialOrd<Self> for Card {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Card {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.strength().cmp(&other.strength())
+    }
+}
+
+const JOKER: Card = Card('J');
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum HandError {
+    WrongLength(usize),
+    InvalidCard(char),
+}
+
+impl Display for HandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WrongLength(length) => write!(f, "{} cards, expected 5", length),
+            InvalidCard(card) => write!(f, "invalid card {}", card),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Hand {
+    cards: [Card; 5],
+    histogram: Vec<(Card, usize)>,
+    jokers: usize,
+}
+
+impl Hand {
+    pub fn parse(str: &str) -> Result<Self, HandError> {
+        if let Some(invalid) = str.chars().find(|char| Card::new(*char).strength() == 0) {
+            return Err(InvalidCard(invalid));
+        }
+
+        let cards: [Card; 5] = str.chars()
+            .map(Card::new)
+            .collect_tuple::<(Card, Card, Card, Card, Card)>()
+            .ok_or_else(|| WrongLength(str.chars().count()))?
+            .into();
+
+        let histogram = make_histogram(cards
+            .iter()
+            .copied()
+            .filter(|card| *card != JOKER)
+            .collect()
+        );
+
+        Ok(Self {
+            cards,
+            histogram,
+            jokers: cards.iter().filter(|card| **card == JOKER).count(),
+        })
+    }
+
+    pub fn nth_combination(&self, rank: usize) -> Option<&(Card, usize)> {
+        if self.histogram.len() > rank { Some(&self.histogram[rank]) } else { None }
+    }
+
+    pub fn rank(&self) -> u32 {
+        let top = self.nth_combination(0).map(|(_, rank)| *rank).unwrap_or_else(|| 0)
+            + self.jokers;
+
+        match top {
+            5 => 7,
+            4 => 6,
+            3 => match self.nth_combination(1) {
+                Some((_, 2)) => 5,
+                _ => 4,
+            },
+            2 => match self.nth_combination(1) {
+                Some((_, 2)) => 3,
+                _ => 2,
+            },
+            1 => 1,
+            _ => 0
+        }
+    }
+
+    pub fn cmp_cards(&self, other: &Self) -> Ordering {
+        for index in 0..5 {
+            if self.cards[index] != other.cards[index] {
+                return self.cards[index].cmp(&other.cards[index]);
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+impl PartialOrd<Self> for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.rank().cmp(&other.rank()) {
+            Ordering::Less => Ordering::Less,
+            Ordering::Greater => Ordering::Greater,
+            Ordering::Equal => self.cmp_cards(other),
+        }
+    }
+}
+
+pub fn parse_input(input: impl BufRead) -> Result<Vec<(Hand, u32)>, String> {
+    input.lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let line = line.map_err(read_error)?;
+            let (hand, bid) = line.split_whitespace()
+                .collect_tuple()
+                .ok_or_else(|| format!("expected a hand and a bid on line {}", index + 1))?;
+
+            Ok((
+                Hand::parse(hand).map_err(|err| format!("invalid hand on line {}: {}", index + 1, err))?,
+                bid.parse().map_err(|err| format!("invalid bid on line {}: {}", index + 1, err))?,
+            ))
+        })
+        .collect()
+}
+
+/// Total winnings with jokers: every hand's bid times its rank.
+pub fn part2(hands: &[(Hand, u32)]) -> Result<Answer, String> {
+    Ok(hands.iter()
+        .sorted()
+        .enumerate()
+        .map(|(rank, (_, bid))| (rank + 1) as u128 * (*bid as u128))
+        .sum::<u128>()
+        .into())
+}
+
+/// Solves part 2 only, this solution plays with jokers.
+pub fn solve(input: impl BufRead) -> Result<Solution, String> {
+    let mut solution = Solution::new(7);
+    let hands = solution.parse(|| parse_input(input))?;
+    solution.solve(2, || part2(&hands))?;
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn cards() -> impl Strategy<Value = String> {
+        prop::collection::vec(prop::sample::select("23456789TJQKA".chars().collect::<Vec<_>>()), 5)
+            .prop_map(String::from_iter)
+    }
+
+    #[test]
+    fn hands_with_the_same_cards_are_equal() {
+        // Ties used to be ordered by whatever order a HashMap gave back
+        for _ in 0..20 {
+            let hand = Hand::parse("23432").unwrap();
+
+            assert_eq!(hand.histogram, vec![(Card('3'), 2), (Card('2'), 2), (Card('4'), 1)]);
+            assert_eq!(hand, Hand::parse("23432").unwrap());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn hands_are_totally_ordered(a in cards(), b in cards(), c in cards()) {
+            let (a, b, c) = (Hand::parse(&a).unwrap(), Hand::parse(&b).unwrap(), Hand::parse(&c).unwrap());
+
+            prop_assert_eq!(a.cmp(&a), Ordering::Equal);
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+
+            if a <= b && b <= c {
+                prop_assert!(a <= c);
+            }
+        }
+
+        #[test]
+        fn hands_of_a_type_are_ordered_by_their_cards(a in cards(), b in cards()) {
+            let (a, b) = (Hand::parse(&a).unwrap(), Hand::parse(&b).unwrap());
+
+            if a.rank() == b.rank() {
+                prop_assert_eq!(a.cmp(&b), a.cmp_cards(&b));
+            } else {
+                prop_assert_eq!(a.cmp(&b), a.rank().cmp(&b.rank()));
+            }
+        }
+
+        #[test]
+        fn the_same_cards_make_equal_hands(cards in cards()) {
+            prop_assert_eq!(Hand::parse(&cards).unwrap(), Hand::parse(&cards).unwrap());
+        }
+    }
+}
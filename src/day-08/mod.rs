@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::answer::Answer;
+use crate::input::read_error;
+use crate::solution::Solution;
+
+#[derive(Debug)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct NodeId([char; 3]);
+
+impl NodeId {
+    pub fn new(str: &str) -> Result<Self, String> {
+        Ok(Self(str
+            .chars()
+            .collect_tuple::<(char, char, char)>()
+            .ok_or_else(|| format!("node {} is not 3 characters", str))?
+            .into()
+        ))
+    }
+
+    pub fn is_start(&self) -> bool {
+        self.0[2] == 'A'
+    }
+
+    pub fn is_finish(&self) -> bool {
+        self.0[2] == 'Z'
+    }
+}
+
+impl Display for NodeId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|char| write!(f, "{}", char))
+    }
+}
+
+pub const START: NodeId = NodeId(['A'; 3]);
+pub const FINISH: NodeId = NodeId(['Z'; 3]);
+
+/// The left/right instructions and the left and right node of every node.
+#[derive(Debug)]
+pub struct Network {
+    pub directions: Vec<Direction>,
+    pub nodes: HashMap<NodeId, (NodeId, NodeId)>,
+}
+
+impl Network {
+    /// Number of steps from `start` until `finished` holds for the current node. Fails for a
+    /// node without a left and right node, or when the walk goes round in circles without ever
+    /// finishing: once it has taken more steps than there are nodes times directions.
+    pub fn steps(&self, start: &NodeId, finished: impl Fn(&NodeId) -> bool) -> Result<u128, String> {
+        let mut current_node = start;
+        let mut steps: u128 = 0;
+        let max_steps = self.nodes.len() as u128 * self.directions.len() as u128;
+
+        while !finished(current_node) {
+            if steps > max_steps {
+                return Err(format!("never finishes when starting from {}", start));
+            }
+
+            let direction_index = (steps % self.directions.len() as u128) as usize;
+            let direction = &self.directions[direction_index];
+            let (left_node, right_node) = self.nodes.get(current_node)
+                .ok_or_else(|| format!("node {} has no left and right node", current_node))?;
+
+            current_node = match direction {
+                Direction::Left => left_node,
+                Direction::Right => right_node
+            };
+
+            steps += 1;
+        }
+
+        Ok(steps)
+    }
+
+    pub fn start_nodes(&self) -> Vec<&NodeId> {
+        self.nodes.keys()
+            .filter(|key| key.is_start())
+            .collect()
+    }
+}
+
+fn parse_directions(input: &mut impl BufRead) -> Result<Vec<Direction>, String> {
+    let mut buffer = String::new();
+
+    if input.read_line(&mut buffer).map_err(read_error)? == 0 {
+        return Err("no directions".to_string());
+    }
+
+    let directions: Vec<Direction> = buffer.trim()
+        .chars()
+        .map(|char| match char {
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(format!("invalid direction {}", char)),
+        })
+        .collect::<Result<_, _>>()?;
+
+    if directions.is_empty() {
+        return Err("no directions".to_string());
+    }
+
+    buffer.clear();
+    if input.read_line(&mut buffer).map_err(read_error)? == 0 || !buffer.trim().is_empty() {
+        return Err("expected an empty line after the directions".to_string());
+    }
+
+    Ok(directions)
+}
+
+fn parse_nodes(input: &mut impl BufRead) -> Result<HashMap<NodeId, (NodeId, NodeId)>, String> {
+    let mut directions: HashMap<NodeId, (NodeId, NodeId)> = HashMap::new();
+    let mut buffer = String::new();
+    let re = Regex::new(r"(\w{3}) = \((\w{3}), (\w{3})\)").unwrap();
+
+    // The nodes start after the directions and an empty line
+    let mut line = 2;
+
+    while input.read_line(&mut buffer).map_err(read_error)? != 0 {
+        line += 1;
+
+        let captures = re.captures(buffer.as_str())
+            .ok_or_else(|| format!("expected a node on line {}", line))?;
+
+        let start = NodeId::new(captures.get(1).unwrap().as_str())?;
+        let left = NodeId::new(captures.get(2).unwrap().as_str())?;
+        let right = NodeId::new(captures.get(3).unwrap().as_str())?;
+
+        directions.insert(start, (left, right));
+
+        buffer.clear();
+    }
+
+    Ok(directions)
+}
+
+pub fn parse_input(mut input: impl BufRead) -> Result<Network, String> {
+    Ok(Network {
+        directions: parse_directions(&mut input)?,
+        nodes: parse_nodes(&mut input)?,
+    })
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Steps from `AAA` to `ZZZ`.
+pub fn part1(network: &Network) -> Result<Answer, String> {
+    network.steps(&START, |node| *node == FINISH).map(Answer::from)
+}
+
+/// Steps until every `xxA` node is on a `xxZ` node at once. Each of them loops back to the start of
+/// its cycle right after reaching its `xxZ` node, so that is the least common multiple of the steps
+/// every node needs on its own.
+pub fn part2(network: &Network) -> Result<Answer, String> {
+    network.start_nodes()
+        .into_iter()
+        .map(|start| network.steps(start, NodeId::is_finish))
+        .try_fold(1, |total: u128, steps| {
+            let steps = steps?;
+            (total / gcd(total, steps)).checked_mul(steps)
+                .ok_or_else(|| "steps don't fit in 128 bits".to_string())
+        })
+        .map(Answer::from)
+}
+
+pub fn solve(input: impl BufRead) -> Result<Solution, String> {
+    let mut solution = Solution::new(8);
+    let network = solution.parse(|| parse_input(input))?;
+
+    // The examples for part two have no AAA node to start from
+    if network.nodes.contains_key(&START) {
+        solution.solve(1, || part1(&network))?;
+    }
+    solution.solve(2, || part2(&network))?;
+    Ok(solution)
+}
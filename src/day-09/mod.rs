@@ -0,0 +1,124 @@
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::input::read_error;
+use crate::solution::Solution;
+
+use sequence::Sequence;
+
+pub mod sequence;
+
+pub fn parse_input(mut input: impl BufRead) -> Result<Vec<Sequence>, String> {
+    let mut buffer = String::new();
+    let mut line = 0;
+    let mut sequences = Vec::new();
+
+    while input.read_line(&mut buffer).map_err(read_error)? != 0 {
+        line += 1;
+
+        let numbers: Vec<i64> = buffer.split_whitespace()
+            .map(|num| num.parse().map_err(|err| format!("invalid number {} on line {}: {}", num, line, err)))
+            .collect::<Result<_, _>>()?;
+
+        if !numbers.is_empty() {
+            sequences.push(Sequence::new(numbers));
+        }
+
+        buffer.clear();
+    }
+
+    Ok(sequences)
+}
+
+/// The sequence followed by its differences, down to all zeros. `None` when a difference doesn't
+/// fit in an `i64`.
+pub fn get_differences_list(sequence: &Sequence) -> Option<Vec<Sequence>> {
+    let mut differences = vec![sequence.clone()];
+
+    while !differences.last().unwrap().is_zeros() {
+        differences.push(differences.last().unwrap().get_difference_sequence()?);
+    }
+
+    Some(differences)
+}
+
+pub fn get_next_number(differences: &[Sequence]) -> Option<i64> {
+    let steps = differences.len() - 1;
+    let mut next_number: i64 = 0;
+
+    for step in 0..steps {
+        let difference_index = steps - 1 - step;
+        let last = differences[difference_index].last();
+        next_number = next_number.checked_add(*last)?;
+    }
+
+    Some(next_number)
+}
+
+pub fn get_previous_number(differences: &[Sequence]) -> Option<i64> {
+    let steps = differences.len() - 1;
+    let mut next_number: i64 = 0;
+
+    for step in 0..steps {
+        let difference_index = steps - 1 - step;
+        let first = differences[difference_index].first();
+        next_number = first.checked_sub(next_number)?;
+    }
+
+    Some(next_number)
+}
+
+/// Sums `extrapolate` over the differences of every sequence.
+fn sum_extrapolated(sequences: &[Sequence], extrapolate: fn(&[Sequence]) -> Option<i64>) -> Option<i64> {
+    sequences.iter()
+        .try_fold(0_i64, |total, sequence| {
+            let value = extrapolate(&get_differences_list(sequence)?)?;
+            total.checked_add(value)
+        })
+}
+
+/// Sum of the next value of every sequence.
+pub fn part1(sequences: &[Sequence]) -> Result<Answer, String> {
+    sum_extrapolated(sequences, get_next_number)
+        .map(Answer::from)
+        .ok_or_else(|| "next values don't fit in 64 bits".to_string())
+}
+
+/// Sum of the previous value of every sequence.
+pub fn part2(sequences: &[Sequence]) -> Result<Answer, String> {
+    sum_extrapolated(sequences, get_previous_number)
+        .map(Answer::from)
+        .ok_or_else(|| "previous values don't fit in 64 bits".to_string())
+}
+
+pub fn solve(input: impl BufRead) -> Result<Solution, String> {
+    let mut solution = Solution::new(9);
+    let sequences = solution.parse(|| parse_input(input))?;
+    solution.solve_both(|| part1(&sequences), || part2(&sequences))?;
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// The value at `x` of the polynomial with `coefficients`, lowest power first.
+    fn evaluate(coefficients: &[i64], x: i64) -> i64 {
+        coefficients.iter().rev().fold(0, |value, coefficient| value * x + coefficient)
+    }
+
+    proptest! {
+        #[test]
+        fn extrapolates_polynomials(coefficients in prop::collection::vec(-20..=20_i64, 1..=6), extra in 1..=10_i64) {
+            // The differences of a polynomial of degree n only reach zero with n + 2 values or more
+            let length = coefficients.len() as i64 + extra;
+            let sequence = Sequence::new((0..length).map(|x| evaluate(&coefficients, x)).collect());
+            let differences = get_differences_list(&sequence).unwrap();
+
+            prop_assert_eq!(get_next_number(&differences), Some(evaluate(&coefficients, length)));
+            prop_assert_eq!(get_previous_number(&differences), Some(evaluate(&coefficients, -1)));
+        }
+    }
+}
@@ -3,7 +3,7 @@ pub struct Sequence(Vec<i64>);
 
 impl Sequence {
     pub fn new(numbers: Vec<i64>) -> Self {
-        if numbers.len() == 0 {
+        if numbers.is_empty() {
             panic!("Empty numbers array");
         }
 
@@ -14,15 +14,16 @@ impl Sequence {
         self.0.iter().all(|num| *num == 0i64)
     }
 
-    pub fn get_difference_sequence(&self) -> Self {
+    /// The differences between consecutive numbers, or `None` when one doesn't fit in an `i64`.
+    pub fn get_difference_sequence(&self) -> Option<Self> {
         let next_length = self.0.len() - 1;
         let mut numbers = Vec::with_capacity(next_length);
 
         for index in 0..next_length {
-            numbers.push(self.0[index + 1] - self.0[index]);
+            numbers.push(self.0[index + 1].checked_sub(self.0[index])?);
         }
 
-        Self(numbers)
+        Some(Self(numbers))
     }
 
     pub fn last(&self) -> &i64 {
@@ -0,0 +1,122 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::{env, process, thread};
+
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+
+use advent_of_code_2023::answers::Report;
+use advent_of_code_2023::input::{self, Input, InputError, Source};
+use advent_of_code_2023::output::Output;
+use advent_of_code_2023::solution::{self, Solution, DAYS};
+use advent_of_code_2023::timings::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// How running a day went.
+enum Run {
+    Skipped(InputError),
+    Solved(Source, Solution),
+    Failed(String),
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+/// Opens the day's input and solves it, turning an invalid input or a panic into a failure of this
+/// day only.
+fn run(day: u8, source: &Source) -> Run {
+    let mut input = match Input::open(day, source.clone()) {
+        Ok(input) => input,
+        Err(err) => return Run::Skipped(err),
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| solution::solve(day, &mut input))) {
+        Ok(Ok(solution)) => Run::Solved(input.source.clone(), solution),
+        Ok(Err(err)) => Run::Failed(err),
+        Err(payload) => Run::Failed(panic_message(payload)),
+    }
+}
+
+/// Takes `--jobs N` out of `args`, defaulting to the available parallelism.
+fn jobs(args: &mut Vec<String>) -> Result<usize, InputError> {
+    let Some(jobs) = input::take_value(args, "--jobs")? else {
+        return Ok(thread::available_parallelism().map_or(1, |jobs| jobs.get()));
+    };
+
+    match jobs.parse() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => Err(InputError::InvalidArgument(format!("invalid value {} for --jobs, expected a positive number", jobs))),
+    }
+}
+
+/// Runs every day on its input from the `inputs/` store, skipping days without one. Takes
+/// `--example N`, `--record`, `--timings` and `--format` like the day binaries, and runs the days
+/// and their parts on `--jobs N` threads. The results are printed in order of the days once all
+/// of them are done; a day with an invalid input is reported as failed without stopping the others.
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let output = input::exit_on_error(Output::from_args(&mut args));
+    let jobs = input::exit_on_error(jobs(&mut args));
+    let record = input::take_flag(&mut args, "--record");
+
+    // Piped stdin can't be the input of every day, so it falls back to the real inputs
+    let source = match input::exit_on_error(input::parse_source(&mut args)) {
+        Source::Stdin => Source::Real,
+        Source::Path(path) => input::exit_on_error(Err(format!("--input {} can't be used for every day", path.display()))),
+        source => source,
+    };
+
+    if let Some(arg) = args.first() {
+        input::exit_on_error(Err(format!("unknown argument {}", arg)))
+    }
+
+    let pool = input::exit_on_error(ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|err| format!("could not start {} threads: {}", jobs, err)));
+
+    let runs: Vec<(u8, Run)> = pool.install(|| {
+        DAYS.par_iter()
+            .map(|&day| (day, run(day, &source)))
+            .collect()
+    });
+
+    let mut solutions = vec![];
+    let mut results = vec![];
+    let mut failed = 0;
+
+    // Every day reads and saves the registry, so they are checked one after the other
+    for (day, run) in runs {
+        match run {
+            Run::Skipped(err) => eprintln!("skipping day {:02}: {}", day, err),
+            Run::Failed(message) => {
+                eprintln!("day {:02} failed: {}", day, message);
+                failed += 1;
+            }
+            Run::Solved(source, solution) => {
+                let mut report_args = if record { vec!["--record".to_string()] } else { vec![] };
+                let mut report = Report::new(day, &source, &mut report_args);
+
+                results.extend(report.results(&solution));
+                report.finish();
+
+                solutions.push(solution);
+            }
+        }
+    }
+
+    output.print(&results, &solutions);
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
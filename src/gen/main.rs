@@ -0,0 +1,55 @@
+use std::env;
+use std::io::{self, Write};
+
+use advent_of_code_2023::generate;
+
+/// Takes `flag` and its numeric value out of `args`.
+fn take_value(args: &mut Vec<String>, flag: &str) -> Option<u64> {
+    let index = args.iter().position(|arg| arg == flag)?;
+
+    args.remove(index);
+    if index >= args.len() {
+        panic!("Missing value for {}", flag);
+    }
+
+    let value = args.remove(index);
+    Some(value.parse().unwrap_or_else(|_| panic!("Invalid value {} for {}, expected a number", value, flag)))
+}
+
+/// Prints a random input for `day`, the same for the same `--seed S`. What `--size N` counts
+/// depends on the day: lines, games, the side of the schematic, cards, mappings per map, races,
+/// hands, the length of the longest loop in the network, or sequences.
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let size = take_value(&mut args, "--size").unwrap_or(100);
+    let seed = take_value(&mut args, "--seed").unwrap_or(2023);
+
+    let day: u8 = match args.first() {
+        Some(day) => day.parse().unwrap_or_else(|_| panic!("Unknown argument {}", day)),
+        None => panic!("Missing day, usage: gen <day> --size N --seed S"),
+    };
+
+    if let Some(arg) = args.get(1) {
+        panic!("Unknown argument {}", arg);
+    }
+
+    let input = match day {
+        1 => generate::day01(size as usize, seed),
+        2 => generate::day02(u16::try_from(size).expect("Day 2 has at most 65535 games"), seed),
+        3 => generate::day03(size as usize, seed),
+        4 => generate::day04(size as usize, seed),
+        5 if size == 0 || size > generate::MAX_MAPPINGS as u64 =>
+            panic!("Day 5 has between 1 and {} mappings per map", generate::MAX_MAPPINGS),
+        5 => generate::day05(10, size as usize, seed),
+        6 if size > 6 => panic!("Day 6 has at most 6 races, or the big race doesn't fit in 64 bits"),
+        6 => generate::day06(size as usize, seed),
+        7 => generate::day07(size as usize, seed),
+        8 if size.saturating_sub(2).saturating_mul(6) >= generate::MAX_INNER_NODES as u64 =>
+            panic!("Day 8 loops have at most {} nodes, or the node names run out", generate::MAX_INNER_NODES / 6 + 1),
+        8 => generate::day08(6, (size / 2).max(2), size.max(3), seed),
+        9 => generate::day09(size as usize, seed),
+        _ => panic!("No generator for day {}", day),
+    };
+
+    io::stdout().write_all(input.as_bytes()).unwrap();
+}
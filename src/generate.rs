@@ -0,0 +1,323 @@
+use std::fmt::Write;
+
+/// A small xorshift generator, so generated inputs are the same for the same seed.
+pub struct Generator(u64);
+
+impl Generator {
+    /// Scrambles the seed first, so that close seeds still give unrelated inputs.
+    pub fn new(seed: u64) -> Self {
+        let mut state = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        Self((state ^ (state >> 31)).max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `low..high`.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next_u64() % (high - low)
+    }
+
+    /// `count` different numbers in `low..high`.
+    pub fn distinct(&mut self, count: usize, low: u64, high: u64) -> Vec<u64> {
+        let mut numbers: Vec<u64> = Vec::with_capacity(count);
+
+        while numbers.len() < count {
+            let number = self.range(low, high);
+            if !numbers.contains(&number) {
+                numbers.push(number);
+            }
+        }
+
+        numbers
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.next_u64() as usize % items.len()]
+    }
+}
+
+const DIGIT_NAMES: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// Calibration lines mixing letters, digits and spelled out digits.
+pub fn day01(lines: usize, seed: u64) -> String {
+    let mut generator = Generator::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..lines {
+        for _ in 0..generator.range(1, 6) {
+            match generator.range(0, 3) {
+                0 => input.push(char::from_digit(generator.range(1, 10) as u32, 10).unwrap()),
+                1 => input.push_str(DIGIT_NAMES[generator.range(0, 9) as usize]),
+                _ => (0..generator.range(1, 6)).for_each(|_| input.push((b'a' + generator.range(0, 26) as u8) as char)),
+            }
+        }
+
+        input.push_str(DIGIT_NAMES[generator.range(0, 9) as usize]);
+        input.push('\n');
+    }
+
+    input
+}
+
+/// Games of three to six draws of up to twenty cubes of each colour.
+pub fn day02(games: u16, seed: u64) -> String {
+    let mut generator = Generator::new(seed);
+    let mut input = String::new();
+
+    for id in 1..=games {
+        let draws: Vec<String> = (0..generator.range(3, 7))
+            .map(|_| {
+                let cubes: Vec<String> = ["red", "green", "blue"].iter()
+                    .filter_map(|colour| match generator.range(0, 3) {
+                        0 => None,
+                        _ => Some(format!("{} {}", generator.range(1, 21), colour)),
+                    })
+                    .collect();
+
+                if cubes.is_empty() { format!("{} red", generator.range(1, 21)) } else { cubes.join(", ") }
+            })
+            .collect();
+
+        writeln!(input, "Game {}: {}", id, draws.join("; ")).unwrap();
+    }
+
+    input
+}
+
+/// A square schematic with numbers of up to three digits and symbols scattered over it.
+pub fn day03(size: usize, seed: u64) -> String {
+    let mut generator = Generator::new(seed);
+    let mut input = String::with_capacity((size + 1) * size);
+
+    for _ in 0..size {
+        let mut row = String::with_capacity(size);
+
+        while row.len() < size {
+            match generator.range(0, 10) {
+                0..=5 => row.push('.'),
+                6..=8 => {
+                    let number = generator.range(1, 1000).to_string();
+                    if row.len() + number.len() < size {
+                        row.push_str(&number);
+                    }
+                    row.push('.');
+                }
+                _ => row.push(*generator.pick(&['*', '*', '#', '+', '$', '/', '@', '=', '%', '-', '&'])),
+            }
+        }
+
+        row.truncate(size);
+        input.push_str(&row);
+        input.push('\n');
+    }
+
+    input
+}
+
+/// Cards in the shape of the real input, with ten winning numbers and twenty-five numbers each.
+pub fn day04(cards: usize, seed: u64) -> String {
+    let mut generator = Generator::new(seed);
+    let mut input = String::with_capacity(cards * 120);
+
+    for id in 1..=cards {
+        write!(input, "Card {:>7}:", id).unwrap();
+        generator.distinct(10, 1, 100).iter().for_each(|number| write!(input, " {:>2}", number).unwrap());
+        input.push_str(" |");
+        generator.distinct(25, 1, 100).iter().for_each(|number| write!(input, " {:>2}", number).unwrap());
+        input.push('\n');
+    }
+
+    input
+}
+
+const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+/// Most mappings per map, so each one still gets at least two values of the 32-bit range.
+pub const MAX_MAPPINGS: usize = 1 << 31;
+
+/// An almanac from seed to location where every map has `mappings` mappings, spread over the
+/// 32-bit range without overlapping. There must be between 1 and [`MAX_MAPPINGS`] mappings.
+pub fn day05(seed_ranges: usize, mappings: usize, seed: u64) -> String {
+    assert!((1..=MAX_MAPPINGS).contains(&mappings), "{} mappings per map, expected 1 to {}", mappings, MAX_MAPPINGS);
+
+    let mut generator = Generator::new(seed);
+    let mut input = String::from("seeds:");
+
+    for _ in 0..seed_ranges {
+        write!(input, " {} {}", generator.range(0, 1 << 32), generator.range(1, 1 << 28)).unwrap();
+    }
+    input.push('\n');
+
+    let width = (1u64 << 32) / mappings as u64;
+
+    for categories in CATEGORIES.windows(2) {
+        writeln!(input, "\n{}-to-{} map:", categories[0], categories[1]).unwrap();
+
+        for index in 0..mappings as u64 {
+            let range = generator.range(1, width);
+            let source = index * width + generator.range(0, width - range + 1);
+            let destination = generator.range(0, (1 << 32) - range);
+
+            writeln!(input, "{} {} {}", destination, source, range).unwrap();
+        }
+    }
+
+    input
+}
+
+/// Up to six races, few enough for the big race to fit in 64 bits.
+pub fn day06(races: usize, seed: u64) -> String {
+    let mut generator = Generator::new(seed);
+    let races: Vec<(u64, u64)> = (0..races.min(6))
+        .map(|_| {
+            let time = generator.range(32, 100);
+            (time, generator.range(100, (time * time / 4).min(1000)))
+        })
+        .collect();
+
+    let times: Vec<String> = races.iter().map(|(time, _)| format!("{:>3}", time)).collect();
+    let records: Vec<String> = races.iter().map(|(_, record)| format!("{:>3}", record)).collect();
+
+    format!("Time:     {}\nDistance: {}\n", times.join("  "), records.join("  "))
+}
+
+/// Hands of five cards with bids of up to a thousand.
+pub fn day07(hands: usize, seed: u64) -> String {
+    let mut generator = Generator::new(seed);
+    let cards: Vec<char> = "23456789TJQKA".chars().collect();
+    let mut input = String::new();
+
+    for _ in 0..hands {
+        (0..5).for_each(|_| input.push(*generator.pick(&cards)));
+        writeln!(input, " {}", generator.range(1, 1001)).unwrap();
+    }
+
+    input
+}
+
+const NODE_CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const INNER_NODE_CHARS: &[u8] = b"0123456789BCDEFGHIJKLMNOPQRSTUVWXY";
+
+/// How many inner nodes can be named before the names repeat.
+pub const MAX_INNER_NODES: usize = NODE_CHARS.len() * NODE_CHARS.len() * INNER_NODE_CHARS.len();
+
+/// Name of the `index`th node that is neither a start nor a finish.
+fn inner_node(index: usize) -> String {
+    let first = NODE_CHARS[index / (NODE_CHARS.len() * INNER_NODE_CHARS.len()) % NODE_CHARS.len()];
+    let second = NODE_CHARS[index / INNER_NODE_CHARS.len() % NODE_CHARS.len()];
+    let third = INNER_NODE_CHARS[index % INNER_NODE_CHARS.len()];
+
+    String::from_utf8(vec![first, second, third]).unwrap()
+}
+
+/// A network of `ghosts` loops, the first from `AAA` to `ZZZ`. Every `xxA` node leads into a
+/// loop that passes its `xxZ` node and comes back to the node after `xxA`, with loop lengths
+/// between `min_length` and `max_length`. Loops have at most `max_length - 2` inner nodes, and
+/// all of them together must number fewer than [`MAX_INNER_NODES`].
+pub fn day08(ghosts: usize, min_length: u64, max_length: u64, seed: u64) -> String {
+    assert!(
+        max_length.saturating_sub(2).saturating_mul(ghosts as u64) < MAX_INNER_NODES as u64,
+        "{} loops of up to {} nodes run out of node names",
+        ghosts,
+        max_length,
+    );
+
+    let mut generator = Generator::new(seed);
+    let mut input = String::new();
+
+    (0..generator.range(200, 300)).for_each(|_| input.push(if generator.range(0, 2) == 0 { 'L' } else { 'R' }));
+    input.push_str("\n\n");
+
+    let mut inner = 0;
+
+    for ghost in 0..ghosts {
+        let (start, finish) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (format!("{:02}A", ghost), format!("{:02}Z", ghost)),
+        };
+
+        let length = generator.range(min_length, max_length);
+        let nodes: Vec<String> = (1..length).map(|index| inner_node(inner + index as usize)).collect();
+        inner += nodes.len();
+
+        writeln!(input, "{} = ({}, {})", start, nodes[0], nodes[0]).unwrap();
+        for (node, next) in nodes.iter().zip(nodes.iter().skip(1).chain([&finish])) {
+            writeln!(input, "{} = ({}, {})", node, next, next).unwrap();
+        }
+        writeln!(input, "{} = ({}, {})", finish, nodes[0], nodes[0]).unwrap();
+    }
+
+    input
+}
+
+/// Sequences of 21 values of polynomials of a degree up to six.
+pub fn day09(sequences: usize, seed: u64) -> String {
+    let mut generator = Generator::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..sequences {
+        let coefficients: Vec<i64> = (0..generator.range(1, 8))
+            .map(|_| generator.range(0, 21) as i64 - 10)
+            .collect();
+
+        let values: Vec<String> = (0..21i64)
+            .map(|x| coefficients.iter().rev().fold(0, |value, coefficient| value * x + coefficient).to_string())
+            .collect();
+
+        input.push_str(&values.join(" "));
+        input.push('\n');
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::solution;
+
+    fn inputs(seed: u64) -> Vec<(u8, String)> {
+        vec![
+            (1, day01(20, seed)),
+            (2, day02(20, seed)),
+            (3, day03(20, seed)),
+            (4, day04(20, seed)),
+            (5, day05(3, 5, seed)),
+            (6, day06(4, seed)),
+            (7, day07(20, seed)),
+            (8, day08(3, 5, 20, seed)),
+            (9, day09(20, seed)),
+        ]
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(inputs(7), inputs(7));
+        assert_ne!(inputs(7), inputs(8));
+    }
+
+    #[test]
+    fn node_names_are_unique() {
+        let names: HashSet<String> = (0..MAX_INNER_NODES).map(inner_node).collect();
+        assert_eq!(names.len(), MAX_INNER_NODES);
+    }
+
+    #[test]
+    fn every_day_solves_its_generated_input() {
+        for seed in 0..5 {
+            for (day, input) in inputs(seed) {
+                let solution = solution::solve(day, &mut input.as_bytes()).unwrap();
+                assert!(!solution.parts.is_empty(), "day {} seed {}", day, seed);
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{env, process};
+
+/// Where a day reads its puzzle input from.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Source {
+    Stdin,
+    /// The real input, `inputs/<day>.txt`.
+    Real,
+    /// An example input, `inputs/<day>.example<n>.txt`.
+    Example(u32),
+    Path(PathBuf),
+}
+
+impl Source {
+    /// A short name for the input, such as `real` or `example1`.
+    pub fn name(&self) -> String {
+        match self {
+            Source::Stdin => "stdin".to_string(),
+            Source::Real => "real".to_string(),
+            Source::Example(n) => format!("example{}", n),
+            Source::Path(path) => path.display().to_string(),
+        }
+    }
+
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            Source::Stdin => None,
+            Source::Real => Some(inputs_dir().join(format!("{:02}.txt", day))),
+            Source::Example(n) => Some(inputs_dir().join(format!("{:02}.example{}.txt", day, n))),
+            Source::Path(path) => Some(path.clone()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { day: u8, path: PathBuf },
+    Unreadable { path: PathBuf, err: io::Error },
+    InvalidArgument(String),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing { day, path } => write!(
+                f,
+                "no input for day {:02}, expected it at {} (or pass --input PATH, or pipe it to stdin)",
+                day,
+                path.display(),
+            ),
+            InputError::Unreadable { path, err } => write!(f, "could not read {}: {}", path.display(), err),
+            InputError::InvalidArgument(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// The `inputs/` directory next to `Cargo.toml`.
+pub fn inputs_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs")
+}
+
+/// An opened puzzle input.
+pub struct Input {
+    pub source: Source,
+    reader: Box<dyn BufRead>,
+}
+
+impl Input {
+    pub fn open(day: u8, source: Source) -> Result<Self, InputError> {
+        let reader: Box<dyn BufRead> = match source.path(day) {
+            None => Box::new(BufReader::new(io::stdin())),
+            Some(path) => {
+                let file = File::open(&path).map_err(|err| match err.kind() {
+                    io::ErrorKind::NotFound => InputError::Missing { day, path: path.clone() },
+                    _ => InputError::Unreadable { path: path.clone(), err },
+                })?;
+
+                Box::new(BufReader::new(file))
+            }
+        };
+
+        Ok(Self { source, reader })
+    }
+}
+
+impl Read for Input {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl BufRead for Input {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.reader.consume(amount)
+    }
+}
+
+/// Takes `flag` out of `args`, returning whether it was there.
+pub fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    match args.iter().position(|arg| arg == flag) {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Takes `flag` and the value after it out of `args`, if the flag is there.
+pub fn take_value(args: &mut Vec<String>, flag: &str) -> Result<Option<String>, InputError> {
+    let Some(index) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+
+    args.remove(index);
+    if index >= args.len() {
+        return Err(InputError::InvalidArgument(format!("missing value for {}", flag)));
+    }
+
+    Ok(Some(args.remove(index)))
+}
+
+/// Parses the value given for `flag`.
+pub fn parse_value<T: FromStr>(flag: &str, value: &str) -> Result<T, InputError> where T::Err: Display {
+    value.parse()
+        .map_err(|err| InputError::InvalidArgument(format!("invalid value {} for {}: {}", value, flag, err)))
+}
+
+/// Takes `--example N` and `--input PATH` out of `args` and picks the input source. Without
+/// either, piped stdin is used and otherwise the real input.
+pub fn parse_source(args: &mut Vec<String>) -> Result<Source, InputError> {
+    let mut source = None;
+
+    while let Some(index) = args.iter().position(|arg| arg == "--example" || arg == "--input") {
+        let flag = args.remove(index);
+        if index >= args.len() {
+            return Err(InputError::InvalidArgument(format!("missing value for {}", flag)));
+        }
+        let value = args.remove(index);
+
+        source = Some(match flag.as_str() {
+            "--example" => Source::Example(value.parse()
+                .map_err(|_| InputError::InvalidArgument(format!("invalid example number {}", value)))?),
+            _ => Source::Path(PathBuf::from(value)),
+        });
+    }
+
+    Ok(source.unwrap_or_else(|| if io::stdin().is_terminal() { Source::Real } else { Source::Stdin }))
+}
+
+/// Describes a failure to read an input, such as one that isn't UTF-8.
+pub fn read_error(err: io::Error) -> String {
+    format!("could not read input: {}", err)
+}
+
+/// Opens the input for `day` from the command line, returning it with the remaining arguments.
+/// Exits with an error message when the input can't be opened.
+pub fn open(day: u8) -> (Input, Vec<String>) {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    match parse_source(&mut args).and_then(|source| Input::open(day, source)) {
+        Ok(input) => (input, args),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Unwraps the result of parsing arguments or solving, exiting with the error message when they
+/// or the input were invalid.
+pub fn exit_on_error<T, E: Display>(result: Result<T, E>) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    })
+}
@@ -0,0 +1,26 @@
+pub mod answer;
+pub mod answers;
+pub mod generate;
+pub mod input;
+pub mod output;
+pub mod solution;
+pub mod timings;
+
+#[path = "day-01/mod.rs"]
+pub mod day01;
+#[path = "day-02/mod.rs"]
+pub mod day02;
+#[path = "day-03/mod.rs"]
+pub mod day03;
+#[path = "day-04/mod.rs"]
+pub mod day04;
+#[path = "day-05/mod.rs"]
+pub mod day05;
+#[path = "day-06/mod.rs"]
+pub mod day06;
+#[path = "day-07/mod.rs"]
+pub mod day07;
+#[path = "day-08/mod.rs"]
+pub mod day08;
+#[path = "day-09/mod.rs"]
+pub mod day09;
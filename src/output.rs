@@ -0,0 +1,187 @@
+use std::str::FromStr;
+
+use crate::answer::Answer;
+use crate::answers::Verdict;
+use crate::input::{parse_value, take_flag, take_value, InputError};
+use crate::solution::Solution;
+use crate::timings::{self, Measurement};
+
+/// How results are printed, from `--format text|json|csv`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err("expected text, json or csv".to_string()),
+        }
+    }
+}
+
+/// The answer to one part of a day on one input, with its verdict and what it took to get it.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Answer,
+    pub verdict: Verdict,
+    /// Parsing the input, shared by all parts of the day.
+    pub parse: Measurement,
+    pub measurement: Measurement,
+}
+
+impl PartResult {
+    fn verdict_name(&self) -> &str {
+        match self.verdict {
+            Verdict::Correct => "correct",
+            Verdict::Wrong { .. } => "wrong",
+            Verdict::Unknown => "unknown",
+            Verdict::Recorded => "recorded",
+        }
+    }
+
+    fn expected(&self) -> Option<&str> {
+        match &self.verdict {
+            Verdict::Wrong { expected } => Some(expected),
+            _ => None,
+        }
+    }
+}
+
+const CSV_HEADER: &str = "day,part,input,answer,verdict,expected,parse_ns,parse_allocations,parse_bytes,time_ns,allocations,bytes";
+
+fn json_string(str: &str) -> String {
+    let mut escaped = String::with_capacity(str.len() + 2);
+    escaped.push('"');
+
+    for char in str.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            char if char.is_control() => escaped.push_str(&format!("\\u{:04x}", char as u32)),
+            char => escaped.push(char),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(str: &str) -> String {
+    if str.contains([',', '"', '\n']) {
+        format!("\"{}\"", str.replace('"', "\"\""))
+    } else {
+        str.to_string()
+    }
+}
+
+fn print_json(results: &[PartResult]) {
+    println!("[");
+
+    for (index, result) in results.iter().enumerate() {
+        println!(
+            "  {{\"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \"verdict\": \"{}\", \"expected\": {}, \
+            \"parse_ns\": {}, \"parse_allocations\": {}, \"parse_bytes\": {}, \"time_ns\": {}, \"allocations\": {}, \"bytes\": {}}}{}",
+            result.day,
+            result.part,
+            json_string(&result.input),
+            json_string(&result.answer.to_string()),
+            result.verdict_name(),
+            result.expected().map_or("null".to_string(), json_string),
+            result.parse.time.as_nanos(),
+            result.parse.allocations,
+            result.parse.bytes,
+            result.measurement.time.as_nanos(),
+            result.measurement.allocations,
+            result.measurement.bytes,
+            if index + 1 < results.len() { "," } else { "" },
+        );
+    }
+
+    println!("]");
+}
+
+fn print_csv(results: &[PartResult]) {
+    println!("{}", CSV_HEADER);
+
+    for result in results {
+        println!(
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            result.day,
+            result.part,
+            csv_field(&result.input),
+            csv_field(&result.answer.to_string()),
+            result.verdict_name(),
+            csv_field(result.expected().unwrap_or("")),
+            result.parse.time.as_nanos(),
+            result.parse.allocations,
+            result.parse.bytes,
+            result.measurement.time.as_nanos(),
+            result.measurement.allocations,
+            result.measurement.bytes,
+        );
+    }
+}
+
+/// What `--format` and `--timings` ask a binary to print.
+#[derive(Debug, Copy, Clone)]
+pub struct Output {
+    pub format: Format,
+    pub timings: bool,
+}
+
+impl Output {
+    /// Takes `--format` and `--timings` out of `args`.
+    pub fn from_args(args: &mut Vec<String>) -> Result<Self, InputError> {
+        let format = match take_value(args, "--format")? {
+            Some(format) => parse_value("--format", &format)?,
+            None => Format::Text,
+        };
+
+        Ok(Self { format, timings: take_flag(args, "--timings") })
+    }
+
+    /// Whether only the results of the day's parts are printed, instead of a binary's own output.
+    pub fn results_only(&self) -> bool {
+        self.format != Format::Text || self.timings
+    }
+
+    /// Fails for `flag` when only results are printed. For flags that change a binary's own output,
+    /// which `--format` and `--timings` replace.
+    pub fn check_flag(&self, flag: &str) -> Result<(), String> {
+        if self.results_only() {
+            return Err(format!("{} can't be combined with --format or --timings", flag));
+        }
+
+        Ok(())
+    }
+
+    /// Prints the results in the chosen format. The timings table is only added to text, the
+    /// other formats always include the timings of every part.
+    pub fn print(&self, results: &[PartResult], solutions: &[Solution]) {
+        match self.format {
+            Format::Text => {
+                for result in results {
+                    println!("day {:02} part {} = {} ({})", result.day, result.part, result.answer, result.verdict);
+                }
+
+                if self.timings {
+                    timings::print_table(solutions);
+                }
+            }
+            Format::Json => print_json(results),
+            Format::Csv => print_csv(results),
+        }
+    }
+}
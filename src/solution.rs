@@ -0,0 +1,84 @@
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::timings::{measure, Measurement};
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day09};
+
+/// The days with a solution.
+pub const DAYS: [u8; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+/// The answer to one part of a day, and what it took to compute it.
+#[derive(Debug, Clone)]
+pub struct PartSolution {
+    pub part: u8,
+    pub answer: Answer,
+    pub measurement: Measurement,
+}
+
+/// The answers to a day's parts, measuring parsing and every part separately.
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub day: u8,
+    pub parse: Measurement,
+    pub parts: Vec<PartSolution>,
+}
+
+impl Solution {
+    pub fn new(day: u8) -> Self {
+        Self { day, parse: Measurement::default(), parts: Vec::new() }
+    }
+
+    /// Runs a step of parsing the input. Every step adds to the parse measurement.
+    pub fn parse<T>(&mut self, parse: impl FnOnce() -> T) -> T {
+        let (parsed, measurement) = measure(parse);
+        self.parse += measurement;
+        parsed
+    }
+
+    /// Solves a part, keeping its answer unless solving it failed.
+    pub fn solve(&mut self, part: u8, solve: impl FnOnce() -> Result<Answer, String>) -> Result<(), String> {
+        let (answer, measurement) = measure(solve);
+        self.parts.push(PartSolution { part, answer: answer?, measurement });
+        Ok(())
+    }
+
+    /// Solves parts 1 and 2, which only share the parsed input, at the same time when there is a
+    /// thread free for it. Each part is measured on the thread that runs it.
+    pub fn solve_both(
+        &mut self,
+        part1: impl FnOnce() -> Result<Answer, String> + Send,
+        part2: impl FnOnce() -> Result<Answer, String> + Send,
+    ) -> Result<(), String> {
+        let ((answer1, measurement1), (answer2, measurement2)) = rayon::join(|| measure(part1), || measure(part2));
+        self.parts.push(PartSolution { part: 1, answer: answer1?, measurement: measurement1 });
+        self.parts.push(PartSolution { part: 2, answer: answer2?, measurement: measurement2 });
+        Ok(())
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartSolution> {
+        self.parts.iter().find(|solution| solution.part == part)
+    }
+
+    /// Parsing and all parts together.
+    pub fn total(&self) -> Measurement {
+        let mut total = self.parse;
+        self.parts.iter().for_each(|part| total += part.measurement);
+        total
+    }
+}
+
+/// Solves `day` for the input, failing for invalid input or a day without a solution.
+pub fn solve(day: u8, input: &mut dyn BufRead) -> Result<Solution, String> {
+    match day {
+        1 => day01::solve(input),
+        2 => day02::solve(input),
+        3 => day03::solve(input),
+        4 => day04::solve(input),
+        5 => day05::solve(input),
+        6 => day06::solve(input),
+        7 => day07::solve(input),
+        8 => day08::solve(input),
+        9 => day09::solve(input),
+        _ => Err(format!("no solution for day {}", day)),
+    }
+}
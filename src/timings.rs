@@ -0,0 +1,104 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::ops::AddAssign;
+use std::time::{Duration, Instant};
+
+use crate::solution::Solution;
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+}
+
+/// The system allocator, counting the allocations made by each thread. Binaries opt in with
+/// `#[global_allocator]`; without it every [`Measurement`] reports no allocations.
+pub struct CountingAllocator;
+
+fn count(bytes: usize) {
+    // The counters are gone while a thread shuts down, those allocations aren't measured anyway
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+    let _ = BYTES.try_with(|total| total.set(total.get() + bytes as u64));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    /// A reallocation counts as a new allocation of the new size.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Wall-clock time and heap allocations of one phase of a day.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Measurement {
+    pub time: Duration,
+    pub allocations: u64,
+    pub bytes: u64,
+}
+
+impl AddAssign for Measurement {
+    fn add_assign(&mut self, other: Self) {
+        self.time += other.time;
+        self.allocations += other.allocations;
+        self.bytes += other.bytes;
+    }
+}
+
+/// Runs `f` on the current thread, measuring its time and the allocations it makes.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Measurement) {
+    let allocations = ALLOCATIONS.with(Cell::get);
+    let bytes = BYTES.with(Cell::get);
+    let start = Instant::now();
+
+    let result = f();
+
+    let measurement = Measurement {
+        time: start.elapsed(),
+        allocations: ALLOCATIONS.with(Cell::get) - allocations,
+        bytes: BYTES.with(Cell::get) - bytes,
+    };
+
+    (result, measurement)
+}
+
+/// Prints the time of every phase and the allocations of every day, followed by a total row.
+pub fn print_table(solutions: &[Solution]) {
+    println!();
+
+    let time = |measurement: Option<Measurement>| measurement.map_or("-".to_string(), |measurement| format!("{:.2?}", measurement.time));
+    let mut total = Measurement::default();
+
+    println!("{:<6}{:>12}{:>12}{:>12}{:>12}{:>13}{:>14}", "day", "parse", "part 1", "part 2", "total", "allocations", "bytes");
+
+    for solution in solutions {
+        let day = solution.total();
+        total += day;
+
+        println!(
+            "{:<6}{:>12}{:>12}{:>12}{:>12}{:>13}{:>14}",
+            format!("{:02}", solution.day),
+            time(Some(solution.parse)),
+            time(solution.part(1).map(|part| part.measurement)),
+            time(solution.part(2).map(|part| part.measurement)),
+            time(Some(day)),
+            day.allocations,
+            day.bytes,
+        );
+    }
+
+    println!("{:<42}{:>12}{:>13}{:>14}", "total", time(Some(total)), total.allocations, total.bytes);
+}